@@ -0,0 +1,86 @@
+//! Compares the day 1 similarity score against the previous binary search
+//! implementation on generated lists with long runs of repeated values.
+//!
+//! cargo run --release --example day1_similarity -- [rows] [distinct values]
+
+use std::{fmt::Write, time::Instant};
+
+use anyhow::Result;
+
+use aoc::day1::LocationLists;
+
+// xorshift64, enough to spread the generated values
+fn next_random(state: &mut u64) -> u64 {
+    *state ^= *state << 13;
+    *state ^= *state >> 7;
+    *state ^= *state << 17;
+    *state
+}
+
+// The previous implementation: a binary search per value of the left list,
+// then a walk over the run of equal values in the right list.
+fn binary_search_similarity(list_a: &[u64], list_b: &[u64]) -> u64 {
+    let mut similarity = 0;
+    for &a in list_a {
+        let mut occurrences = 0;
+        if let Ok(index) = list_b.binary_search(&a) {
+            occurrences += 1;
+            for &b in list_b[..index].iter().rev() {
+                if b == a {
+                    occurrences += 1;
+                } else {
+                    break;
+                }
+            }
+            for &b in &list_b[index + 1..] {
+                if b == a {
+                    occurrences += 1;
+                } else {
+                    break;
+                }
+            }
+        }
+
+        similarity += a * occurrences;
+    }
+
+    similarity
+}
+
+fn main() -> Result<()> {
+    let mut args = std::env::args().skip(1);
+    let rows = args.next().map_or(Ok(1_000_000), |rows| rows.parse())?;
+    let distinct = args.next().map_or(Ok(1_000), |distinct| distinct.parse())?;
+
+    let mut state = 0x2545_f491_4f6c_dd1d;
+    let mut list_a = Vec::with_capacity(rows);
+    let mut list_b = Vec::with_capacity(rows);
+    let mut input = String::new();
+    for _ in 0..rows {
+        let a = 10_000 + next_random(&mut state) % distinct;
+        let b = 10_000 + next_random(&mut state) % distinct;
+        list_a.push(a);
+        list_b.push(b);
+        writeln!(input, "{a}   {b}")?;
+    }
+    list_a.sort();
+    list_b.sort();
+
+    let lists = LocationLists::from_reader(input.as_bytes())?;
+
+    let start = Instant::now();
+    let merged = lists.similarity()?;
+    let merge_elapsed = start.elapsed();
+
+    let start = Instant::now();
+    let searched = binary_search_similarity(&list_a, &list_b);
+    let search_elapsed = start.elapsed();
+
+    assert_eq!(merged, i128::from(searched));
+
+    println!("{rows} rows, {distinct} distinct values, similarity {merged}");
+    println!("Merge:         {merge_elapsed:?}");
+    println!("Binary search: {search_elapsed:?}");
+
+    Ok(())
+}
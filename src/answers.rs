@@ -0,0 +1,89 @@
+use std::collections::BTreeMap;
+
+use anyhow::{bail, Context, Result};
+
+use crate::runner::{Part, PartResult};
+
+/// Default answers manifest, relative to the repository root.
+pub const DEFAULT_ANSWERS: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/answers.txt");
+
+/// Expected answers keyed by day and part.
+///
+/// The manifest is a plain text table with one `<day> <part> <answer>` entry
+/// per line. Empty lines and lines starting with `#` are ignored.
+pub struct Answers {
+    answers: BTreeMap<(u32, Part), String>,
+}
+
+pub struct Mismatch {
+    pub day: u32,
+    pub part: Part,
+    pub expected: String,
+    pub actual: String,
+}
+
+impl Answers {
+    pub fn from_file(file_name: &str) -> Result<Self> {
+        let input = std::fs::read_to_string(file_name)
+            .with_context(|| format!("cannot read {file_name}"))?;
+
+        Self::parse(&input)
+    }
+
+    pub fn parse(input: &str) -> Result<Self> {
+        let mut answers = BTreeMap::new();
+
+        for (index, line) in input.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let context = || format!("invalid answer at line {}: {line}", index + 1);
+
+            let mut fields = line.split_whitespace();
+            let (Some(day), Some(part), Some(answer), None) =
+                (fields.next(), fields.next(), fields.next(), fields.next())
+            else {
+                bail!(context());
+            };
+
+            let day = day.parse::<u32>().with_context(context)?;
+            let part =
+                Part::from_number(part.parse().with_context(context)?).with_context(context)?;
+
+            if answers.insert((day, part), answer.to_owned()).is_some() {
+                bail!("duplicate answer for day {day} part {part}");
+            }
+        }
+
+        Ok(Self { answers })
+    }
+
+    pub fn get(&self, day: u32, part: Part) -> Option<&str> {
+        self.answers.get(&(day, part)).map(String::as_str)
+    }
+
+    /// Compares computed results against the expected answers. Results
+    /// without a recorded answer are ignored.
+    pub fn verify<'a>(&self, results: impl IntoIterator<Item = &'a PartResult>) -> Vec<Mismatch> {
+        results
+            .into_iter()
+            .filter_map(|result| {
+                let expected = self.get(result.day, result.part)?;
+                let actual = match &result.answer {
+                    Ok(answer) if answer == expected => return None,
+                    Ok(answer) => answer.clone(),
+                    Err(err) => format!("error: {err}"),
+                };
+
+                Some(Mismatch {
+                    day: result.day,
+                    part: result.part,
+                    expected: expected.to_owned(),
+                    actual,
+                })
+            })
+            .collect()
+    }
+}
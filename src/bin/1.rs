@@ -1,109 +1,174 @@
-use std::io::BufRead as _;
+use std::{
+    fmt::Display,
+    io::{BufWriter, Write},
+    path::PathBuf,
+    time::Instant,
+};
 
 use anyhow::Result;
-use clap::Parser;
+use clap::{Parser, ValueEnum};
 
+use aoc::{
+    cli::{json_string, open_input, print_answers, print_result, read_input, DayArgs, Format},
+    day1::{external_distance_and_similarity, Day1, LocationLists},
+    runner::{single_pass, Part},
+    Solution,
+};
+
+/// Day 1: Historian Hysteria
 #[derive(Parser)]
 struct Args {
-    file: String,
+    #[command(flatten)]
+    day: DayArgs,
+
+    /// Print the distance and similarity of every pair of columns instead
+    #[arg(long)]
+    matrix: bool,
+
+    /// Print a CSV report of the first two columns instead
+    #[arg(long, value_enum, value_name = "REPORT", conflicts_with = "matrix")]
+    csv: Option<Report>,
+
+    /// Sort the first two columns on disk, for inputs larger than memory
+    #[arg(long, conflicts_with_all = ["matrix", "csv"])]
+    external: bool,
+
+    /// Location IDs of each column sorted in memory at a time by --external
+    #[arg(long, default_value_t = 1_000_000, requires = "external")]
+    run_size: usize,
+
+    /// Directory for the sorted runs of --external; defaults to the system
+    /// temporary directory
+    #[arg(long, requires = "external")]
+    temp_dir: Option<PathBuf>,
 }
 
-fn stonum(first: Option<char>, chars: &mut std::str::Chars) -> u64 {
-    let mut value: u64 = first.map(|c| c.to_digit(10).unwrap() as u64).unwrap_or(0);
-    while let Some(c) = chars.next() {
-        if c.is_ascii_whitespace() {
-            break;
-        }
-        value = value * 10 + c.to_digit(10).unwrap() as u64;
-    }
-    value
+#[derive(Clone, Copy, ValueEnum)]
+enum Report {
+    /// `left,right,distance` for every pair of IDs after sorting
+    Pairs,
+    /// `id,left_count,right_count,contribution` for every ID in both columns
+    Similarity,
 }
 
-fn skip_whitespace(chars: &mut std::str::Chars) -> Option<char> {
-    while let Some(c) = chars.next() {
-        if !c.is_ascii_whitespace() {
-            return Some(c);
+fn write_csv(mut writer: impl Write, lists: &LocationLists, report: Report) -> Result<()> {
+    match report {
+        Report::Pairs => {
+            writeln!(writer, "left,right,distance")?;
+            for pair in lists.pairs() {
+                writeln!(writer, "{},{},{}", pair.left, pair.right, pair.distance)?;
+            }
+        }
+        Report::Similarity => {
+            writeln!(writer, "id,left_count,right_count,contribution")?;
+            for contribution in lists.contributions() {
+                let contribution = contribution?;
+                writeln!(
+                    writer,
+                    "{},{},{},{}",
+                    contribution.id,
+                    contribution.left_count,
+                    contribution.right_count,
+                    contribution.score
+                )?;
+            }
         }
     }
-    None
-}
 
-fn file_list_distance(file_name: &str) -> Result<u64> {
-    let file = std::fs::File::open(file_name)?;
-    let reader = std::io::BufReader::new(file);
+    writer.flush()?;
 
-    let mut list_a = Vec::new();
-    let mut list_b = Vec::new();
-    for line in reader.lines() {
-        let line = line?;
-
-        let mut chars = line.chars();
+    Ok(())
+}
 
-        list_a.push(stonum(chars.next(), &mut chars));
-        list_b.push(stonum(skip_whitespace(&mut chars), &mut chars));
+fn print_matrix(name: &str, matrix: &[Vec<impl Display>]) {
+    let width = matrix
+        .iter()
+        .flatten()
+        .map(|value| value.to_string().len())
+        .max()
+        .unwrap_or(0)
+        .max(matrix.len().to_string().len());
+
+    print!("{name:>width$}", width = name.len());
+    for column in 1..=matrix.len() {
+        print!("  {column:>width$}");
     }
+    println!();
 
-    list_a.sort();
-    list_b.sort();
-
-    let mut distance = 0;
-    for (a, b) in list_a.iter().zip(list_b.iter()) {
-        distance += a.abs_diff(*b);
+    for (index, row) in matrix.iter().enumerate() {
+        print!("{:>width$}", index + 1, width = name.len());
+        for value in row {
+            print!("  {value:>width$}");
+        }
+        println!();
     }
+}
 
-    Ok(distance)
+fn json_matrix(matrix: &[Vec<impl Display>]) -> String {
+    let rows = matrix
+        .iter()
+        .map(|row| {
+            // Strings, like the answers, as the values can exceed 2^53
+            let values = row
+                .iter()
+                .map(|value| json_string(&value.to_string()))
+                .collect::<Vec<_>>();
+            format!("[{}]", values.join(","))
+        })
+        .collect::<Vec<_>>();
+
+    format!("[{}]", rows.join(","))
 }
 
-fn file_list_similarity(file_name: &str) -> Result<u64> {
-    let file = std::fs::File::open(file_name)?;
-    let reader = std::io::BufReader::new(file);
+fn main() -> Result<()> {
+    let args = Args::parse();
+    let labels = [(Part::One, "Distance"), (Part::Two, "Similarity")];
+
+    if args.external {
+        let temp_dir = args.temp_dir.unwrap_or_else(std::env::temp_dir);
+        let start = Instant::now();
+        let (distance, similarity) = external_distance_and_similarity(
+            open_input(args.day.file.as_deref())?,
+            &temp_dir,
+            args.run_size,
+        )?;
+        let result = single_pass(
+            Day1::DAY,
+            start.elapsed(),
+            [Ok(distance.to_string()), Ok(similarity.to_string())],
+        );
+
+        return print_result(result, args.day.format, &labels);
+    }
 
-    let mut list_a = Vec::new();
-    let mut list_b = Vec::new();
-    for line in reader.lines() {
-        let line = line?;
+    let input = read_input(args.day.file.as_deref())?;
 
-        let mut chars = line.chars();
+    if let Some(report) = args.csv {
+        let lists = LocationLists::from_reader(input.as_bytes())?;
 
-        list_a.push(stonum(chars.next(), &mut chars));
-        list_b.push(stonum(skip_whitespace(&mut chars), &mut chars));
+        return write_csv(BufWriter::new(std::io::stdout().lock()), &lists, report);
     }
 
-    list_a.sort();
-    list_b.sort();
-
-    let mut similarity = 0;
-    for a in list_a {
-        let mut occurrences = 0;
-        if let Ok(index) = list_b.binary_search(&a) {
-            occurrences += 1;
-            for left_index in (0..index).rev() {
-                if list_b[left_index] == a {
-                    occurrences += 1;
-                } else {
-                    break;
-                }
-            }
-            for right_index in index + 1..list_b.len() {
-                if list_b[right_index] == a {
-                    occurrences += 1;
-                } else {
-                    break;
-                }
-            }
-        }
-
-        similarity += a * occurrences;
+    if !args.matrix {
+        return print_answers::<Day1>(&input, args.day.format, &labels);
     }
 
-    Ok(similarity)
-}
+    let lists = LocationLists::from_reader(input.as_bytes())?;
+    let distance = lists.distance_matrix()?;
+    let similarity = lists.similarity_matrix()?;
 
-fn main() -> Result<()> {
-    let args = Args::parse();
-
-    println!("Distance: {}", file_list_distance(&args.file)?);
-    println!("Similarity: {}", file_list_similarity(&args.file)?);
+    match args.day.format {
+        Format::Text => {
+            print_matrix("Distance", &distance);
+            println!();
+            print_matrix("Similarity", &similarity);
+        }
+        Format::Json => println!(
+            "{{\"distance\":{},\"similarity\":{}}}",
+            json_matrix(&distance),
+            json_matrix(&similarity)
+        ),
+    }
 
     Ok(())
 }
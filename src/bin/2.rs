@@ -1,166 +1,252 @@
-use std::io::BufRead as _;
+use std::{
+    io::{BufWriter, Write},
+    time::Instant,
+};
 
 use anyhow::Result;
 use clap::Parser;
 
+use aoc::{
+    cli::{open_input, print_result, read_input, DayArgs, Format},
+    day2::{monitor_feed, safe_counts_from_reader, Day2, ReportState, SafetyPolicy, Verdict},
+    runner::{single_pass, solve, Part},
+    Solution,
+};
+
+/// Day 2: Red-Nosed Reports
 #[derive(Parser)]
 struct Args {
-    file: String,
+    #[command(flatten)]
+    day: DayArgs,
+
+    /// Smallest allowed difference between adjacent levels
+    #[arg(long, default_value_t = SafetyPolicy::default().min_step)]
+    min_step: u32,
+
+    /// Largest allowed difference between adjacent levels
+    #[arg(long, default_value_t = SafetyPolicy::default().max_step)]
+    max_step: u32,
+
+    /// Allow adjacent levels to be equal
+    #[arg(long)]
+    allow_plateaus: bool,
+
+    /// Allow levels to both increase and decrease in the same report
+    #[arg(long)]
+    any_direction: bool,
+
+    /// Most levels the error dampener may remove from a report
+    #[arg(long, default_value_t = 1)]
+    max_removed: usize,
+
+    /// Print why each report is safe or unsafe instead of the counts
+    #[arg(long)]
+    verdicts: bool,
+
+    /// Count while streaming the input, checking reports on this many
+    /// threads; 0 uses every available core
+    #[arg(long, conflicts_with = "verdicts")]
+    threads: Option<usize>,
+
+    /// Follow a live feed of reports, printing the state of a report as soon
+    /// as it changes
+    #[arg(long, conflicts_with_all = ["verdicts", "threads"])]
+    monitor: bool,
 }
 
-fn report_is_safe(report: &str) -> bool {
-    let mut prev: Option<u32> = None;
-    let mut is_positive: Option<bool> = None;
+fn describe(levels: &[u32], verdict: &Verdict) -> String {
+    match verdict {
+        Verdict::Safe => "safe".to_owned(),
+        Verdict::Dampened { removed } => {
+            let removed = removed
+                .iter()
+                .map(|&index| format!("{index} ({})", levels[index]))
+                .collect::<Vec<_>>();
 
-    for level in report.split_whitespace() {
-        let level = level.parse::<u32>().unwrap();
-        if let Some(prev) = prev {
-            let diff = i64::from(level) - i64::from(prev);
-            if !(1..=3).contains(&diff.abs()) {
-                return false;
-            }
-            let current_is_positive = match diff.signum() {
-                1 => true,
-                -1 => false,
-                _ => return false,
-            };
-            if let Some(is_positive) = is_positive {
-                if is_positive != current_is_positive {
-                    return false;
-                }
-            }
-            is_positive = Some(current_is_positive);
+            let plural = if removed.len() > 1 { "s" } else { "" };
+
+            format!("safe after removing level{plural} {}", removed.join(", "))
         }
-        prev = Some(level);
+        Verdict::Unsafe { index, violation } => format!(
+            "unsafe: {violation} between levels {index} and {} ({} -> {})",
+            index + 1,
+            levels[*index],
+            levels[index + 1]
+        ),
     }
-
-    true
 }
 
-fn is_safe(file_name: &str) -> Result<u64> {
-    let file = std::fs::File::open(file_name);
-    let reader = std::io::BufReader::new(file?);
+fn json_list(values: impl IntoIterator<Item = impl ToString>) -> String {
+    let values = values
+        .into_iter()
+        .map(|value| value.to_string())
+        .collect::<Vec<_>>();
 
-    let mut safe_reports = 0;
+    format!("[{}]", values.join(","))
+}
 
-    for line in reader.lines() {
-        let line = line?;
+fn json_verdict(levels: &[u32], verdict: &Verdict) -> String {
+    match verdict {
+        Verdict::Safe => "\"verdict\":\"safe\"".to_owned(),
+        Verdict::Dampened { removed } => format!(
+            "\"verdict\":\"dampened\",\"removed\":{}",
+            json_list(removed)
+        ),
+        Verdict::Unsafe { index, violation } => format!(
+            "\"verdict\":\"unsafe\",\"index\":{index},\"levels\":{},\"reason\":\"{violation}\"",
+            json_list(&levels[*index..index + 2])
+        ),
+    }
+}
 
-        if report_is_safe(&line) {
-            safe_reports += 1;
+fn json_state(state: &ReportState) -> String {
+    match state {
+        ReportState::Safe => "\"state\":\"safe\"".to_owned(),
+        ReportState::Dampened { removed } => {
+            format!("\"state\":\"dampened\",\"removed\":{removed}")
         }
+        ReportState::Unrecoverable => "\"state\":\"unrecoverable\"".to_owned(),
     }
-
-    Ok(safe_reports)
 }
 
-fn report_is_safe_recursive(
-    prev: Option<u32>,
-    is_positive: Option<bool>,
-    dampened: bool,
-    levels: &[u32],
-) -> bool {
-    if levels.is_empty() {
-        return true;
-    }
+/// Writes one line per report, numbered from 1 like the input lines.
+fn write_verdicts(mut writer: impl Write, solution: &Day2, format: Format) -> Result<()> {
+    let reports = solution.reports().iter().zip(solution.verdicts());
 
-    let level = levels[0];
-    if let Some(prev) = prev {
-        // println!("  {} vs {}", prev, level);
-
-        let diff = i64::from(level) - i64::from(prev);
-        let check_levels = || {
-            let current_is_positive = match diff.signum() {
-                1 => true,
-                -1 => false,
-                _ => {
-                    // println!("  {} == {}", prev, level);
-                    return Err(())
-                },
-            };
-
-            if !(1..=3).contains(&diff.abs()) {
-                // println!("  {} is too far from {}", level, prev);
-                return Err(());
+    match format {
+        Format::Text => {
+            writeln!(writer, "Report  Verdict")?;
+            for (number, (levels, verdict)) in reports.enumerate() {
+                writeln!(writer, "{:>6}  {}", number + 1, describe(levels, &verdict))?;
             }
-            if let Some(is_positive) = is_positive {
-                if is_positive != current_is_positive {
-                    // println!("  {} has the wrong sign", level);
-                    return Err(());
-                }
+        }
+        Format::Json => {
+            writeln!(writer, "[")?;
+            let count = solution.reports().len();
+            for (number, (levels, verdict)) in reports.enumerate() {
+                let separator = if number + 1 < count { "," } else { "" };
+                writeln!(
+                    writer,
+                    "  {{\"report\":{},{}}}{separator}",
+                    number + 1,
+                    json_verdict(levels, &verdict)
+                )?;
             }
-            Ok(current_is_positive)
-        };
+            writeln!(writer, "]")?;
+        }
+    }
 
-        let skip_current = || {
-            if dampened {
-                return false;
-            }
-            // println!("  Skipping level {}", level);
-            report_is_safe_recursive(Some(prev), is_positive, true, &levels[1..])
-        };
+    writer.flush()?;
 
-        let Ok(current_is_positive) = check_levels() else {
-            return skip_current();
-        };
+    Ok(())
+}
 
-        if report_is_safe_recursive(
-            Some(level),
-            Some(current_is_positive),
-            dampened,
-            &levels[1..],
-        ) {
-            return true;
+fn main() -> Result<()> {
+    let args = Args::parse();
+    let labels = [
+        (Part::One, "Safe reports"),
+        (Part::Two, "Safe reports with error dampening"),
+    ];
+
+    let policy = SafetyPolicy {
+        min_step: args.min_step,
+        max_step: args.max_step,
+        allow_plateaus: args.allow_plateaus,
+        consistent_direction: !args.any_direction,
+    };
+
+    if args.monitor {
+        let mut stdout = std::io::stdout().lock();
+        let mut state = ReportState::Safe;
+        let mut count = 0;
+
+        if matches!(args.day.format, Format::Json) {
+            writeln!(stdout, "[")?;
         }
 
-        return skip_current();
-    }
+        monitor_feed(
+            open_input(args.day.file.as_deref())?,
+            &policy,
+            args.max_removed,
+            |event| {
+                if event.index == 0 {
+                    state = ReportState::Safe;
+                }
+                if event.state != state {
+                    state = event.state;
+                    match args.day.format {
+                        Format::Text => writeln!(
+                            stdout,
+                            "Report {} level {} ({}): {state}",
+                            event.report, event.index, event.level
+                        )?,
+                        Format::Json => {
+                            if count > 0 {
+                                writeln!(stdout, ",")?;
+                            }
+                            write!(
+                                stdout,
+                                "  {{\"report\":{},\"index\":{},\"level\":{},{}}}",
+                                event.report,
+                                event.index,
+                                event.level,
+                                json_state(&state)
+                            )?;
+                        }
+                    }
+                    count += 1;
+                    stdout.flush()?;
+                }
 
-    if report_is_safe_recursive(Some(level), is_positive, dampened, &levels[1..]) {
-        return true;
-    }
+                Ok(())
+            },
+        )?;
 
-    if !dampened {
-        // Skip the current level and try again
-        // println!("  Skipping head level {}", level);
-        return report_is_safe_recursive(None, is_positive, true, &levels[1..]);
-    }
+        if matches!(args.day.format, Format::Json) {
+            if count > 0 {
+                writeln!(stdout)?;
+            }
+            writeln!(stdout, "]")?;
+        }
 
-    false
-}
+        return Ok(());
+    }
 
-fn is_safe_with_dampening(file_name: &str) -> Result<u64> {
-    let file = std::fs::File::open(file_name);
-    let reader = std::io::BufReader::new(file?);
+    if let Some(threads) = args.threads {
+        let threads = match threads {
+            0 => std::thread::available_parallelism()?.get(),
+            threads => threads,
+        };
+        let start = Instant::now();
+        let counts = safe_counts_from_reader(
+            open_input(args.day.file.as_deref())?,
+            &policy,
+            args.max_removed,
+            threads,
+        )?;
+        let result = single_pass(
+            Day2::DAY,
+            start.elapsed(),
+            [Ok(counts.safe.to_string()), Ok(counts.dampened.to_string())],
+        );
+
+        return print_result(result, args.day.format, &labels);
+    }
 
-    let mut safe_reports = 0;
+    let input = read_input(args.day.file.as_deref())?;
 
-    for line in reader.lines() {
-        let line = line?;
+    let start = Instant::now();
+    let solution = Day2::parse(&input)?
+        .with_policy(policy)
+        .with_max_removed(args.max_removed);
 
-        // println!("{}", line);
-        let levels: Vec<u32> = line
-            .split_whitespace()
-            .map(|level| level.parse().unwrap())
-            .collect();
+    if args.verdicts {
+        let stdout = BufWriter::new(std::io::stdout().lock());
 
-        if report_is_safe_recursive(None, None, false, &levels) {
-            // println!("  Safe");
-            safe_reports += 1;
-        }
+        return write_verdicts(stdout, &solution, args.day.format);
     }
 
-    Ok(safe_reports)
-}
-
-fn main() -> Result<()> {
-    let args = Args::parse();
+    let result = solve(&solution, start.elapsed(), None);
 
-    println!("Safe reports: {}", is_safe(&args.file)?);
-    println!(
-        "Safe reports with error dampening: {}",
-        is_safe_with_dampening(&args.file)?
-    );
-
-    Ok(())
+    print_result(result, args.day.format, &labels)
 }
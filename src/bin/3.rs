@@ -1,79 +1,140 @@
-use std::io::Read;
+use std::{
+    io::{BufWriter, Write},
+    time::Instant,
+};
 
 use anyhow::Result;
 use clap::Parser;
 
+use aoc::{
+    cli::{json_string, open_input, print_result, read_input, DayArgs, Format},
+    day3::{Day3, Lexeme, Lexer, Machine},
+    runner::{single_pass, solve, Part},
+    Solution,
+};
+
+/// Day 3: Mull It Over
 #[derive(Parser)]
 struct Args {
-    file: String,
-}
+    #[command(flatten)]
+    day: DayArgs,
 
-fn sanitized_mult(file_name: &str) -> Result<u64> {
-    let file = std::fs::File::open(file_name)?;
-    let mut reader = std::io::BufReader::new(file);
+    /// Scan the input as it is read, for inputs larger than memory
+    #[arg(long)]
+    stream: bool,
 
-    let mut sum = 0;
+    /// Only accept operands of 1 to 3 digits
+    #[arg(long)]
+    strict: bool,
 
-    // Read the whole file
-    let mut buffer = String::new();
-    reader.read_to_string(&mut buffer)?;
+    /// List the malformed `mul` instructions and why they were rejected
+    /// instead of the sums
+    #[arg(long, conflicts_with = "stream")]
+    near_misses: bool,
+}
 
-    let re = regex::Regex::new(r"mul\((\d+),(\d+)\)")?;
+fn write_near_misses(
+    mut writer: impl Write,
+    lexer: Lexer<impl std::io::BufRead>,
+    format: Format,
+) -> Result<()> {
+    let mut count = 0;
 
-    for expr in re.captures_iter(&buffer) {
-        let a: u64 = expr[1].parse()?;
-        let b: u64 = expr[2].parse()?;
+    match format {
+        Format::Text => writeln!(writer, "Offset  Line  Column  Near miss")?,
+        Format::Json => writeln!(writer, "[")?,
+    }
 
-        sum += a * b;
+    for lexeme in lexer.lexemes() {
+        let Lexeme::NearMiss(near_miss) = lexeme? else {
+            continue;
+        };
+
+        match format {
+            Format::Text => writeln!(
+                writer,
+                "{:>6}  {:>4}  {:>6}  {:?}: {}",
+                near_miss.offset,
+                near_miss.line,
+                near_miss.column,
+                near_miss.text,
+                near_miss.rejection
+            )?,
+            Format::Json => {
+                if count > 0 {
+                    writeln!(writer, ",")?;
+                }
+                write!(
+                    writer,
+                    "  {{\"offset\":{},\"line\":{},\"column\":{},\"text\":{},\"reason\":{}}}",
+                    near_miss.offset,
+                    near_miss.line,
+                    near_miss.column,
+                    json_string(&near_miss.text),
+                    json_string(&near_miss.rejection.to_string())
+                )?;
+            }
+        }
+        count += 1;
     }
 
-    Ok(sum)
+    match format {
+        Format::Text => writeln!(writer, "{count} near misses")?,
+        Format::Json => {
+            if count > 0 {
+                writeln!(writer)?;
+            }
+            writeln!(writer, "]")?;
+        }
+    }
+    writer.flush()?;
+
+    Ok(())
 }
 
-fn sanitized_mult_with_conditions(file_name: &str) -> Result<u64> {
-    let file = std::fs::File::open(file_name)?;
-    let mut reader = std::io::BufReader::new(file);
+fn main() -> Result<()> {
+    let args = Args::parse();
+    let labels = [(Part::One, "Sum"), (Part::Two, "Sum with conditions")];
 
-    let mut sum = 0;
+    if args.near_misses {
+        let lexer = Lexer::new(open_input(args.day.file.as_deref())?).strict(args.strict);
 
-    // Read the whole file
-    let mut buffer = String::new();
-    reader.read_to_string(&mut buffer)?;
+        return write_near_misses(
+            BufWriter::new(std::io::stdout().lock()),
+            lexer,
+            args.day.format,
+        );
+    }
 
-    let re = regex::Regex::new(r"mul\((\d+),(\d+)\)|do\(\)|don't\(\)")?;
+    if !args.stream {
+        let input = read_input(args.day.file.as_deref())?;
 
-    let mut enabled = true;
+        let start = Instant::now();
+        let solution = Day3::parse_strict(&input, args.strict)?;
+        let result = solve(&solution, start.elapsed(), None);
 
-    for expr in re.captures_iter(&buffer) {
-        let expr_str = expr.get(0).unwrap().as_str();
+        return print_result(result, args.day.format, &labels);
+    }
 
-        if expr_str == "do()" {
-            enabled = true;
-            continue;
-        } else if expr_str == "don't()" {
-            enabled = false;
-            continue;
-        } else if !enabled {
-            continue;
+    // Run both parts in a single read; a part that fails stops executing
+    let start = Instant::now();
+    let mut machines = [Machine::new(false), Machine::new(true)].map(Ok);
+    let lexer = Lexer::new(open_input(args.day.file.as_deref())?).strict(args.strict);
+    for token in lexer {
+        let instruction = token?.instruction;
+        for machine in &mut machines {
+            if let Ok(running) = machine {
+                if let Err(err) = running.execute(instruction) {
+                    *machine = Err(err);
+                }
+            }
         }
-
-        let a: u64 = expr[1].parse()?;
-        let b: u64 = expr[2].parse()?;
-
-        sum += a * b;
     }
+    let answers = machines.map(|machine| machine.map(|machine| machine.sum.to_string()));
 
-    Ok(sum)
-}
-
-fn main() -> Result<()> {
-    let args = Args::parse();
-
-    println!("Sum: {}", sanitized_mult(&args.file)?);
-    println!(
-        "Sum with conditions: {}",
-        sanitized_mult_with_conditions(&args.file)?
-    );
-
-    Ok(())
+    print_result(
+        single_pass(Day3::DAY, start.elapsed(), answers),
+        args.day.format,
+        &labels,
+    )
 }
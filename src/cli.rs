@@ -0,0 +1,169 @@
+use std::{
+    fs::File,
+    io::{BufRead, BufReader, Read, Write},
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+use clap::{Parser, ValueEnum};
+
+use crate::{
+    runner::{run, DayResult, Part, PartResult},
+    Solution,
+};
+
+#[derive(Clone, Copy, Default, ValueEnum)]
+pub enum Format {
+    /// Human-readable answers
+    #[default]
+    Text,
+    /// JSON array of `{day, part, answer, elapsed_ns, parse_ns}` records
+    Json,
+}
+
+/// Command line of the per-day binaries.
+#[derive(Parser)]
+pub struct DayArgs {
+    /// Puzzle input, or `-` to read it from stdin (the default)
+    pub file: Option<PathBuf>,
+
+    /// Output format
+    #[arg(long, value_enum, default_value_t)]
+    pub format: Format,
+}
+
+/// Reads the whole puzzle input from `file`, or from stdin if it is `-` or
+/// `None`.
+pub fn read_input(file: Option<&Path>) -> Result<String> {
+    let mut input = String::new();
+
+    match file {
+        Some(file) if file != Path::new("-") => {
+            input = std::fs::read_to_string(file)
+                .with_context(|| format!("cannot read {}", file.display()))?;
+        }
+        _ => {
+            std::io::stdin()
+                .lock()
+                .read_to_string(&mut input)
+                .context("cannot read stdin")?;
+        }
+    }
+
+    Ok(input)
+}
+
+/// Opens the puzzle input for streaming, from `file` or from stdin if it is
+/// `-` or `None`.
+pub fn open_input(file: Option<&Path>) -> Result<Box<dyn BufRead>> {
+    match file {
+        Some(file) if file != Path::new("-") => {
+            let input =
+                File::open(file).with_context(|| format!("cannot open {}", file.display()))?;
+
+            Ok(Box::new(BufReader::new(input)))
+        }
+        _ => Ok(Box::new(std::io::stdin().lock())),
+    }
+}
+
+pub fn format_answer(result: &PartResult) -> String {
+    match &result.answer {
+        Ok(answer) => answer.clone(),
+        Err(err) => format!("error: {err}"),
+    }
+}
+
+pub fn json_string(s: &str) -> String {
+    let mut json = String::with_capacity(s.len() + 2);
+
+    json.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if c.is_control() => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+
+    json
+}
+
+/// Writes one record per solved part as a JSON array. Answers are strings so
+/// that large values survive consumers that parse numbers as doubles.
+pub fn write_json(mut writer: impl Write, results: &[DayResult]) -> Result<()> {
+    let records = results
+        .iter()
+        .flat_map(|day| day.parts.iter().map(move |part| (part, day.parse_elapsed)))
+        .collect::<Vec<_>>();
+
+    writeln!(writer, "[")?;
+
+    for (index, (result, parse_elapsed)) in records.iter().enumerate() {
+        let answer = match &result.answer {
+            Ok(answer) => format!("\"answer\":{}", json_string(answer)),
+            Err(err) => format!(
+                "\"answer\":null,\"error\":{}",
+                json_string(&err.to_string())
+            ),
+        };
+        let separator = if index + 1 < records.len() { "," } else { "" };
+
+        writeln!(
+            writer,
+            "  {{\"day\":{},\"part\":{},{answer},\"elapsed_ns\":{},\"parse_ns\":{}}}{separator}",
+            result.day,
+            result.part,
+            result.elapsed.as_nanos(),
+            parse_elapsed.as_nanos()
+        )?;
+    }
+
+    writeln!(writer, "]")?;
+
+    Ok(())
+}
+
+/// Entry point of the per-day binaries. `labels` names the answer of each
+/// solved part in the text output.
+pub fn day_main<S: Solution>(labels: &[(Part, &str)]) -> Result<()> {
+    let args = DayArgs::parse();
+
+    let input = read_input(args.file.as_deref())?;
+
+    print_answers::<S>(&input, args.format, labels)
+}
+
+/// Solves `input` and prints the answers like [`day_main`], for binaries with
+/// extra options of their own.
+pub fn print_answers<S: Solution>(
+    input: &str,
+    format: Format,
+    labels: &[(Part, &str)],
+) -> Result<()> {
+    let part = match labels {
+        [(part, _)] => Some(*part),
+        _ => None,
+    };
+
+    print_result(run::<S>(input, part)?, format, labels)
+}
+
+/// Prints the answers of a solved day like [`day_main`].
+pub fn print_result(result: DayResult, format: Format, labels: &[(Part, &str)]) -> Result<()> {
+    match format {
+        Format::Text => {
+            for (result, (_, label)) in result.parts.into_iter().zip(labels) {
+                println!("{label}: {}", result.answer?);
+            }
+        }
+        Format::Json => write_json(std::io::stdout().lock(), &[result])?,
+    }
+
+    Ok(())
+}
@@ -0,0 +1,382 @@
+use std::{cmp::Ordering, io::BufRead, num::IntErrorKind, path::Path};
+
+use anyhow::{bail, Context, Result};
+
+use crate::{
+    external_sort::ExternalSorter,
+    parse::{fields, ParseError},
+    Solution,
+};
+
+/// A location ID. IDs may be negative and go well beyond `u64`.
+pub type LocationId = i128;
+
+fn location_id(line: usize, column: usize, field: &str) -> Result<LocationId, ParseError> {
+    field.parse().map_err(|err: std::num::ParseIntError| {
+        let reason = match err.kind() {
+            IntErrorKind::PosOverflow | IntErrorKind::NegOverflow => "number is too large",
+            _ => "invalid number",
+        };
+
+        ParseError::new(Day1::DAY, line, column, field, reason)
+    })
+}
+
+/// Parses the location IDs of every line and passes them to `row`. The
+/// number of columns, at least two, is set by the first line.
+fn parse_rows(
+    reader: impl BufRead,
+    mut row: impl FnMut(Vec<LocationId>) -> Result<()>,
+) -> Result<()> {
+    let mut columns = None;
+    for (index, line) in reader.lines().enumerate() {
+        let line = line?;
+
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let fields = fields(&line).collect::<Vec<_>>();
+
+        if fields.len() < columns.unwrap_or(2) {
+            let column = line.chars().count() + 1;
+            bail!(ParseError::new(
+                Day1::DAY,
+                index + 1,
+                column,
+                line.as_str(),
+                "missing location ID"
+            ));
+        }
+        let columns = *columns.get_or_insert(fields.len());
+        if let Some(&(column, extra)) = fields.get(columns) {
+            bail!(ParseError::new(
+                Day1::DAY,
+                index + 1,
+                column,
+                extra,
+                "unexpected field"
+            ));
+        }
+
+        row(fields
+            .into_iter()
+            .map(|(column, id)| location_id(index + 1, column, id))
+            .collect::<Result<_, _>>()?)?;
+    }
+
+    Ok(())
+}
+
+/// Parses one location list per column.
+fn parse_lists(reader: impl BufRead) -> Result<Vec<Vec<LocationId>>> {
+    let mut lists: Vec<Vec<LocationId>> = Vec::new();
+    parse_rows(reader, |ids| {
+        if lists.is_empty() {
+            lists.resize_with(ids.len(), Vec::new);
+        }
+        for (list, id) in lists.iter_mut().zip(ids) {
+            list.push(id);
+        }
+
+        Ok(())
+    })?;
+
+    if lists.is_empty() {
+        lists.resize_with(2, Vec::new);
+    }
+
+    Ok(lists)
+}
+
+/// Two location IDs at the same rank in their sorted lists.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Pair {
+    pub left: LocationId,
+    pub right: LocationId,
+    pub distance: u128,
+}
+
+/// The share of the similarity score of an ID found in both lists.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Contribution {
+    pub id: LocationId,
+    pub left_count: usize,
+    pub right_count: usize,
+    /// `id * left_count * right_count`
+    pub score: i128,
+}
+
+impl Contribution {
+    /// Returns `None` if the score does not fit in an `i128`.
+    fn new(id: LocationId, left_count: usize, right_count: usize) -> Option<Self> {
+        let score = id
+            .checked_mul(left_count as i128)?
+            .checked_mul(right_count as i128)?;
+
+        Some(Self {
+            id,
+            left_count,
+            right_count,
+            score,
+        })
+    }
+}
+
+/// The location lists, one per column, parsed once and kept sorted so that
+/// distances and similarities can be computed without sorting again.
+pub struct LocationLists {
+    lists: Vec<Vec<LocationId>>,
+}
+
+impl LocationLists {
+    pub fn from_file(file_name: &str) -> Result<Self> {
+        let file = std::fs::File::open(file_name)?;
+
+        Self::from_reader(std::io::BufReader::new(file))
+    }
+
+    pub fn from_reader(reader: impl BufRead) -> Result<Self> {
+        let mut lists = parse_lists(reader)?;
+
+        for list in &mut lists {
+            list.sort();
+        }
+
+        Ok(Self { lists })
+    }
+
+    /// Number of lists, one per column of the input.
+    pub fn columns(&self) -> usize {
+        self.lists.len()
+    }
+
+    /// Distance between the first two lists.
+    pub fn distance(&self) -> Result<u128> {
+        self.distance_between(0, 1)
+    }
+
+    /// Similarity of the first list to the second one.
+    pub fn similarity(&self) -> Result<i128> {
+        self.similarity_between(0, 1)
+    }
+
+    /// Errors if the distance does not fit in a `u128`.
+    pub fn distance_between(&self, a: usize, b: usize) -> Result<u128> {
+        let mut distance: u128 = 0;
+        for (id_a, id_b) in self.lists[a].iter().zip(self.lists[b].iter()) {
+            distance = distance
+                .checked_add(id_a.abs_diff(*id_b))
+                .with_context(|| overflow("distance", a, b))?;
+        }
+
+        Ok(distance)
+    }
+
+    /// Similarity of the values of list `a` to list `b`. Errors if the
+    /// similarity does not fit in an `i128`.
+    pub fn similarity_between(&self, a: usize, b: usize) -> Result<i128> {
+        let mut similarity: i128 = 0;
+        for contribution in self.contributions_between(a, b) {
+            similarity = contribution?
+                .score
+                .checked_add(similarity)
+                .with_context(|| overflow("similarity", a, b))?;
+        }
+
+        Ok(similarity)
+    }
+
+    /// The IDs at the same rank in the first two lists, with the distance
+    /// between them.
+    pub fn pairs(&self) -> impl Iterator<Item = Pair> + '_ {
+        self.lists[0]
+            .iter()
+            .zip(self.lists[1].iter())
+            .map(|(&left, &right)| Pair {
+                left,
+                right,
+                distance: left.abs_diff(right),
+            })
+    }
+
+    /// What each ID found in both of the first two lists adds to their
+    /// similarity, in ascending ID order.
+    pub fn contributions(&self) -> impl Iterator<Item = Result<Contribution>> + '_ {
+        self.contributions_between(0, 1)
+    }
+
+    /// Merges the sorted lists `a` and `b`, so every run of equal values is
+    /// visited once regardless of how long it is.
+    fn contributions_between(
+        &self,
+        a: usize,
+        b: usize,
+    ) -> impl Iterator<Item = Result<Contribution>> + '_ {
+        let (list_a, list_b) = (&self.lists[a], &self.lists[b]);
+        let (mut index_a, mut index_b) = (0, 0);
+
+        std::iter::from_fn(move || {
+            while index_a < list_a.len() && index_b < list_b.len() {
+                let id_a = list_a[index_a];
+                let id_b = list_b[index_b];
+
+                match id_a.cmp(&id_b) {
+                    Ordering::Less => index_a += 1,
+                    Ordering::Greater => index_b += 1,
+                    Ordering::Equal => {
+                        let run_a = list_a[index_a..].partition_point(|&id| id == id_a);
+                        let run_b = list_b[index_b..].partition_point(|&id| id == id_b);
+
+                        index_a += run_a;
+                        index_b += run_b;
+
+                        return Some(
+                            Contribution::new(id_a, run_a, run_b)
+                                .with_context(|| overflow("similarity", a, b)),
+                        );
+                    }
+                }
+            }
+
+            None
+        })
+    }
+
+    /// Distances between every pair of lists, indexed by column.
+    pub fn distance_matrix(&self) -> Result<Vec<Vec<u128>>> {
+        (0..self.columns())
+            .map(|a| {
+                (0..self.columns())
+                    .map(|b| self.distance_between(a, b))
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Similarities between every pair of lists, indexed by column.
+    pub fn similarity_matrix(&self) -> Result<Vec<Vec<i128>>> {
+        (0..self.columns())
+            .map(|a| {
+                (0..self.columns())
+                    .map(|b| self.similarity_between(a, b))
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+fn overflow(what: &str, a: usize, b: usize) -> String {
+    format!("{what} of columns {} and {} overflows", a + 1, b + 1)
+}
+
+/// Runs of equal values in sorted `ids`, with their length.
+fn runs(
+    ids: impl Iterator<Item = Result<LocationId>>,
+) -> impl Iterator<Item = Result<(LocationId, usize)>> {
+    let mut ids = ids.peekable();
+
+    std::iter::from_fn(move || {
+        let id = match ids.next()? {
+            Ok(id) => id,
+            Err(err) => return Some(Err(err)),
+        };
+
+        let mut count = 1;
+        while ids
+            .next_if(|next| matches!(next, Ok(next) if *next == id))
+            .is_some()
+        {
+            count += 1;
+        }
+
+        Some(Ok((id, count)))
+    })
+}
+
+/// Distance and similarity of the first two columns, like [`LocationLists`]
+/// but with at most `run_size` IDs of each column in memory. The columns are
+/// sorted in runs spilled to `temp_dir`, then merged twice: by rank for the
+/// distance and by value for the similarity.
+pub fn external_distance_and_similarity(
+    reader: impl BufRead,
+    temp_dir: &Path,
+    run_size: usize,
+) -> Result<(u128, i128)> {
+    let mut sorter_a = ExternalSorter::new(temp_dir, run_size)?;
+    let mut sorter_b = ExternalSorter::new(temp_dir, run_size)?;
+    parse_rows(reader, |ids| {
+        sorter_a.push(ids[0])?;
+        sorter_b.push(ids[1])
+    })?;
+    let (list_a, list_b) = (sorter_a.finish()?, sorter_b.finish()?);
+
+    let mut distance: u128 = 0;
+    for (id_a, id_b) in list_a.iter()?.zip(list_b.iter()?) {
+        distance = distance
+            .checked_add(id_a?.abs_diff(id_b?))
+            .with_context(|| overflow("distance", 0, 1))?;
+    }
+
+    let mut similarity: i128 = 0;
+    let mut runs_a = runs(list_a.iter()?);
+    let mut runs_b = runs(list_b.iter()?);
+    let (mut run_a, mut run_b) = (runs_a.next().transpose()?, runs_b.next().transpose()?);
+    while let (Some((id_a, count_a)), Some((id_b, count_b))) = (run_a, run_b) {
+        match id_a.cmp(&id_b) {
+            Ordering::Less => run_a = runs_a.next().transpose()?,
+            Ordering::Greater => run_b = runs_b.next().transpose()?,
+            Ordering::Equal => {
+                similarity = Contribution::new(id_a, count_a, count_b)
+                    .and_then(|contribution| contribution.score.checked_add(similarity))
+                    .with_context(|| overflow("similarity", 0, 1))?;
+
+                run_a = runs_a.next().transpose()?;
+                run_b = runs_b.next().transpose()?;
+            }
+        }
+    }
+
+    Ok((distance, similarity))
+}
+
+pub fn list_distance_from_reader(reader: impl BufRead) -> Result<u128> {
+    LocationLists::from_reader(reader)?.distance()
+}
+
+pub fn list_similarity_from_reader(reader: impl BufRead) -> Result<i128> {
+    LocationLists::from_reader(reader)?.similarity()
+}
+
+pub fn file_list_distance(file_name: &str) -> Result<u128> {
+    LocationLists::from_file(file_name)?.distance()
+}
+
+pub fn file_list_similarity(file_name: &str) -> Result<i128> {
+    LocationLists::from_file(file_name)?.similarity()
+}
+
+pub struct Day1 {
+    lists: LocationLists,
+}
+
+impl Solution for Day1 {
+    const DAY: u32 = 1;
+
+    type Answer1 = u128;
+    type Answer2 = i128;
+
+    fn parse(input: &str) -> Result<Self> {
+        Ok(Self {
+            lists: LocationLists::from_reader(input.as_bytes())?,
+        })
+    }
+
+    fn part1(&self) -> Result<u128> {
+        self.lists.distance()
+    }
+
+    fn part2(&self) -> Result<i128> {
+        self.lists.similarity()
+    }
+}
@@ -0,0 +1,768 @@
+use std::{
+    collections::VecDeque,
+    fmt::Display,
+    io::BufRead,
+    sync::{
+        mpsc::{sync_channel, SyncSender},
+        Mutex,
+    },
+};
+
+use anyhow::Result;
+
+use crate::{
+    parse::{fields, number, ParseError},
+    Solution,
+};
+
+fn parse_report(index: usize, line: &str) -> Result<Vec<u32>, ParseError> {
+    fields(line)
+        .map(|(column, level)| number(Day2::DAY, index + 1, column, level))
+        .collect()
+}
+
+/// Why two adjacent levels make a report unsafe.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Violation {
+    StepTooSmall,
+    StepTooLarge,
+    Plateau,
+    DirectionChange,
+}
+
+impl Display for Violation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let reason = match self {
+            Self::StepTooSmall => "step too small",
+            Self::StepTooLarge => "step too large",
+            Self::Plateau => "plateau",
+            Self::DirectionChange => "direction change",
+        };
+
+        f.pad(reason)
+    }
+}
+
+/// The outcome of checking one report.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Safe,
+    /// Safe once the levels at these indices are removed.
+    Dampened {
+        removed: Vec<usize>,
+    },
+    /// The levels at `index` and `index + 1` are the first pair to break the
+    /// policy, and the dampener cannot fix the report.
+    Unsafe {
+        index: usize,
+        violation: Violation,
+    },
+}
+
+/// The reactor tolerances a report must meet to be safe.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SafetyPolicy {
+    /// Smallest allowed difference between adjacent levels, plateaus aside.
+    pub min_step: u32,
+    /// Largest allowed difference between adjacent levels.
+    pub max_step: u32,
+    /// Whether adjacent levels may be equal.
+    pub allow_plateaus: bool,
+    /// Whether the levels must all increase or all decrease.
+    pub consistent_direction: bool,
+}
+
+impl Default for SafetyPolicy {
+    /// The puzzle rules: levels all increase or all decrease, by 1 to 3.
+    fn default() -> Self {
+        Self {
+            min_step: 1,
+            max_step: 3,
+            allow_plateaus: false,
+            consistent_direction: true,
+        }
+    }
+}
+
+impl SafetyPolicy {
+    /// Checks the step from `prev` to `level`, given the direction of the
+    /// report so far, and returns the direction after it.
+    pub fn check_step(
+        &self,
+        prev: u32,
+        level: u32,
+        is_positive: Option<bool>,
+    ) -> Result<Option<bool>, Violation> {
+        let step = level.abs_diff(prev);
+        if step == 0 {
+            return if self.allow_plateaus {
+                Ok(is_positive)
+            } else {
+                Err(Violation::Plateau)
+            };
+        }
+
+        if step < self.min_step {
+            return Err(Violation::StepTooSmall);
+        }
+        if step > self.max_step {
+            return Err(Violation::StepTooLarge);
+        }
+
+        let current_is_positive = level > prev;
+        if self.consistent_direction && is_positive.is_some_and(|p| p != current_is_positive) {
+            return Err(Violation::DirectionChange);
+        }
+
+        Ok(Some(current_is_positive))
+    }
+}
+
+/// The index of the first level that cannot follow the one before it, and
+/// why.
+pub fn first_violation(policy: &SafetyPolicy, levels: &[u32]) -> Option<(usize, Violation)> {
+    let mut is_positive: Option<bool> = None;
+
+    for (index, window) in levels.windows(2).enumerate() {
+        match policy.check_step(window[0], window[1], is_positive) {
+            Ok(direction) => is_positive = direction,
+            Err(violation) => return Some((index, violation)),
+        }
+    }
+
+    None
+}
+
+pub fn report_is_safe(policy: &SafetyPolicy, levels: &[u32]) -> bool {
+    first_violation(policy, levels).is_none()
+}
+
+pub fn is_safe(file_name: &str, policy: &SafetyPolicy) -> Result<u64> {
+    let file = std::fs::File::open(file_name)?;
+
+    is_safe_from_reader(std::io::BufReader::new(file), policy)
+}
+
+pub fn is_safe_from_reader(reader: impl BufRead, policy: &SafetyPolicy) -> Result<u64> {
+    let mut safe_reports = 0;
+
+    for (index, line) in reader.lines().enumerate() {
+        let line = line?;
+
+        if report_is_safe(policy, &parse_report(index, &line)?) {
+            safe_reports += 1;
+        }
+    }
+
+    Ok(safe_reports)
+}
+
+pub fn report_is_safe_recursive(
+    policy: &SafetyPolicy,
+    prev: Option<u32>,
+    is_positive: Option<bool>,
+    dampened: bool,
+    levels: &[u32],
+) -> bool {
+    if levels.is_empty() {
+        return true;
+    }
+
+    let level = levels[0];
+    if let Some(prev) = prev {
+        let skip_current = || {
+            if dampened {
+                return false;
+            }
+            report_is_safe_recursive(policy, Some(prev), is_positive, true, &levels[1..])
+        };
+
+        let Ok(current_is_positive) = policy.check_step(prev, level, is_positive) else {
+            return skip_current();
+        };
+
+        if report_is_safe_recursive(
+            policy,
+            Some(level),
+            current_is_positive,
+            dampened,
+            &levels[1..],
+        ) {
+            return true;
+        }
+
+        return skip_current();
+    }
+
+    if report_is_safe_recursive(policy, Some(level), is_positive, dampened, &levels[1..]) {
+        return true;
+    }
+
+    if !dampened {
+        // Skip the current level and try again
+        return report_is_safe_recursive(policy, None, is_positive, true, &levels[1..]);
+    }
+
+    false
+}
+
+fn direction_index(is_positive: Option<bool>) -> usize {
+    match is_positive {
+        None => 0,
+        Some(true) => 1,
+        Some(false) => 2,
+    }
+}
+
+const DIRECTIONS: [Option<bool>; 3] = [None, Some(true), Some(false)];
+
+/// The state of a report as its levels arrive.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReportState {
+    Safe,
+    /// Safe once `removed` levels are removed.
+    Dampened {
+        removed: usize,
+    },
+    /// No more than the allowed levels can be removed to make the report
+    /// safe, whatever levels come next.
+    Unrecoverable,
+}
+
+impl Display for ReportState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Safe => write!(f, "safe"),
+            Self::Dampened { removed: 1 } => write!(f, "safe after removing 1 level"),
+            Self::Dampened { removed } => write!(f, "safe after removing {removed} levels"),
+            Self::Unrecoverable => write!(f, "unrecoverable"),
+        }
+    }
+}
+
+/// Checks a report one level at a time. For every level, keeps the fewest
+/// removals that leave it as the last kept level in each direction. A kept
+/// level can only follow one of the `max_removed + 1` levels before it, so
+/// only those are kept, and each level takes O(max_removed) steps instead of
+/// trying every subset.
+pub struct ReportChecker {
+    policy: SafetyPolicy,
+    max_removed: usize,
+    len: usize,
+    /// The last levels, with their fewest removals per direction.
+    recent: VecDeque<(u32, [Option<usize>; 3])>,
+    state: ReportState,
+}
+
+impl ReportChecker {
+    pub fn new(policy: SafetyPolicy, max_removed: usize) -> Self {
+        Self {
+            policy,
+            max_removed,
+            len: 0,
+            recent: VecDeque::with_capacity(max_removed + 1),
+            state: ReportState::Safe,
+        }
+    }
+
+    /// Adds the next level of the report and returns the new state.
+    pub fn push(&mut self, level: u32) -> ReportState {
+        if self.state == ReportState::Unrecoverable {
+            self.len += 1;
+            return self.state;
+        }
+
+        let mut removals = [None; 3];
+        if self.len <= self.max_removed {
+            removals[direction_index(None)] = Some(self.len);
+        }
+
+        // The most recent level is skipped by none, the oldest by max_removed
+        for (skipped, &(prev, prev_removals)) in self.recent.iter().rev().enumerate() {
+            for (index, is_positive) in DIRECTIONS.into_iter().enumerate() {
+                let Some(removed) = prev_removals[index] else {
+                    continue;
+                };
+                let removed = removed + skipped;
+                if removed > self.max_removed {
+                    continue;
+                }
+
+                if let Ok(is_positive) = self.policy.check_step(prev, level, is_positive) {
+                    let best = &mut removals[direction_index(is_positive)];
+                    *best = Some(best.map_or(removed, |best| best.min(removed)));
+                }
+            }
+        }
+
+        if self.recent.len() > self.max_removed {
+            self.recent.pop_front();
+        }
+        self.recent.push_back((level, removals));
+        self.len += 1;
+
+        // Remove every level after the last kept one
+        let fewest = self
+            .recent
+            .iter()
+            .rev()
+            .enumerate()
+            .flat_map(|(trailing, (_, removals))| {
+                removals
+                    .iter()
+                    .flatten()
+                    .map(move |removed| removed + trailing)
+            })
+            .filter(|&removed| removed <= self.max_removed)
+            .min();
+
+        self.state = match fewest {
+            Some(0) => ReportState::Safe,
+            Some(removed) => ReportState::Dampened { removed },
+            None => ReportState::Unrecoverable,
+        };
+
+        self.state
+    }
+
+    pub fn state(&self) -> ReportState {
+        self.state
+    }
+
+    /// Number of levels pushed since the start of the report.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Starts the next report.
+    pub fn reset(&mut self) {
+        self.len = 0;
+        self.recent.clear();
+        self.state = ReportState::Safe;
+    }
+}
+
+/// Fewest levels to remove for the rest of the report to be safe, if no more
+/// than `max_removed`.
+pub fn min_removals(policy: &SafetyPolicy, levels: &[u32], max_removed: usize) -> Option<usize> {
+    let mut checker = ReportChecker::new(*policy, max_removed);
+    for &level in levels {
+        checker.push(level);
+    }
+
+    match checker.state() {
+        ReportState::Safe => Some(0),
+        ReportState::Dampened { removed } => Some(removed),
+        ReportState::Unrecoverable => None,
+    }
+}
+
+/// The indices of the fewest levels to remove for the rest of the report to
+/// be safe, found like [`ReportChecker`] does while remembering the kept
+/// level before each one.
+pub fn removed_levels(
+    policy: &SafetyPolicy,
+    levels: &[u32],
+    max_removed: usize,
+) -> Option<Vec<usize>> {
+    /// Fewest removals leaving a level last, and the kept level before it.
+    #[derive(Clone, Copy)]
+    struct Best {
+        removed: usize,
+        prev: Option<(usize, usize)>,
+    }
+
+    if levels.is_empty() {
+        return Some(Vec::new());
+    }
+
+    let mut best: Vec<[Option<Best>; 3]> = vec![[None; 3]; levels.len()];
+    let mut fewest: Option<(usize, (usize, usize))> = None;
+
+    for last in 0..levels.len() {
+        if last <= max_removed {
+            best[last][direction_index(None)] = Some(Best {
+                removed: last,
+                prev: None,
+            });
+        }
+
+        for prev in last.saturating_sub(max_removed + 1)..last {
+            let skipped = last - prev - 1;
+
+            for (index, is_positive) in DIRECTIONS.into_iter().enumerate() {
+                let Some(Best { removed, .. }) = best[prev][index] else {
+                    continue;
+                };
+                let removed = removed + skipped;
+                if removed > max_removed {
+                    continue;
+                }
+
+                if let Ok(is_positive) = policy.check_step(levels[prev], levels[last], is_positive)
+                {
+                    let entry = &mut best[last][direction_index(is_positive)];
+                    if entry.is_none_or(|entry| removed < entry.removed) {
+                        *entry = Some(Best {
+                            removed,
+                            prev: Some((prev, index)),
+                        });
+                    }
+                }
+            }
+        }
+
+        // Remove every level after this one
+        let trailing = levels.len() - last - 1;
+        for (index, entry) in best[last].into_iter().enumerate() {
+            let Some(Best { removed, .. }) = entry else {
+                continue;
+            };
+            let removed = removed + trailing;
+            if removed <= max_removed && fewest.is_none_or(|(fewest, _)| removed < fewest) {
+                fewest = Some((removed, (last, index)));
+            }
+        }
+    }
+
+    // Walk back through the kept levels and collect the ones in between
+    let (_, mut kept) = fewest?;
+    let mut removed = ((kept.0 + 1)..levels.len()).collect::<Vec<_>>();
+    while let Some(prev) = best[kept.0][kept.1].and_then(|entry| entry.prev) {
+        removed.extend(prev.0 + 1..kept.0);
+        kept = prev;
+    }
+    removed.extend(0..kept.0);
+    removed.sort_unstable();
+
+    Some(removed)
+}
+
+/// Whether the report is safe after removing up to `max_removed` levels.
+pub fn report_is_safe_dampened(policy: &SafetyPolicy, levels: &[u32], max_removed: usize) -> bool {
+    min_removals(policy, levels, max_removed).is_some()
+}
+
+/// Checks a report, removing up to `max_removed` levels if needed.
+pub fn verdict(policy: &SafetyPolicy, levels: &[u32], max_removed: usize) -> Verdict {
+    let Some((index, violation)) = first_violation(policy, levels) else {
+        return Verdict::Safe;
+    };
+
+    match removed_levels(policy, levels, max_removed) {
+        Some(removed) => Verdict::Dampened { removed },
+        None => Verdict::Unsafe { index, violation },
+    }
+}
+
+pub fn is_safe_with_dampening(file_name: &str, policy: &SafetyPolicy) -> Result<u64> {
+    let file = std::fs::File::open(file_name)?;
+
+    is_safe_with_dampening_from_reader(std::io::BufReader::new(file), policy)
+}
+
+pub fn is_safe_with_dampening_from_reader(
+    reader: impl BufRead,
+    policy: &SafetyPolicy,
+) -> Result<u64> {
+    let mut safe_reports = 0;
+
+    for (index, line) in reader.lines().enumerate() {
+        let line = line?;
+
+        let levels = parse_report(index, &line)?;
+
+        if report_is_safe_recursive(policy, None, None, false, &levels) {
+            safe_reports += 1;
+        }
+    }
+
+    Ok(safe_reports)
+}
+
+/// A level read by [`monitor_feed`], and the state of its report after it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FeedEvent {
+    /// The report number, which is also its line number.
+    pub report: usize,
+    /// Index of the level in its report.
+    pub index: usize,
+    pub level: u32,
+    pub state: ReportState,
+}
+
+/// Parses the level in `token`, if any, and pushes it to the checker.
+fn end_level(
+    checker: &mut ReportChecker,
+    on_level: &mut impl FnMut(FeedEvent) -> Result<()>,
+    line: usize,
+    column: usize,
+    token: &mut Vec<u8>,
+) -> Result<()> {
+    if token.is_empty() {
+        return Ok(());
+    }
+
+    let level = number(Day2::DAY, line, column, &String::from_utf8_lossy(token))?;
+    token.clear();
+
+    let index = checker.len();
+    let state = checker.push(level);
+
+    on_level(FeedEvent {
+        report: line,
+        index,
+        level,
+        state,
+    })
+}
+
+/// Checks the reports of a live feed, one per line, calling `on_level` as
+/// soon as each level is complete instead of waiting for the end of its
+/// line.
+pub fn monitor_feed(
+    reader: impl BufRead,
+    policy: &SafetyPolicy,
+    max_removed: usize,
+    mut on_level: impl FnMut(FeedEvent) -> Result<()>,
+) -> Result<()> {
+    let mut checker = ReportChecker::new(*policy, max_removed);
+    let mut line = 1;
+    let mut column = 1;
+    let mut token = Vec::new();
+    let mut token_column = 1;
+
+    for byte in reader.bytes() {
+        let byte = byte?;
+
+        if byte.is_ascii_whitespace() {
+            end_level(&mut checker, &mut on_level, line, token_column, &mut token)?;
+        } else {
+            if token.is_empty() {
+                token_column = column;
+            }
+            token.push(byte);
+        }
+
+        if byte == b'\n' {
+            line += 1;
+            column = 1;
+            checker.reset();
+        } else if byte & 0xc0 != 0x80 {
+            // Count characters, not UTF-8 continuation bytes
+            column += 1;
+        }
+    }
+
+    end_level(&mut checker, &mut on_level, line, token_column, &mut token)
+}
+
+/// Number of safe reports, without and with the error dampener.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SafeCounts {
+    pub safe: u64,
+    pub dampened: u64,
+}
+
+impl SafeCounts {
+    fn add(self, other: Self) -> Self {
+        Self {
+            safe: self.safe + other.safe,
+            dampened: self.dampened + other.dampened,
+        }
+    }
+}
+
+/// Lines handed to a worker thread at a time.
+const CHUNK_LINES: usize = 4096;
+
+fn count_chunk(
+    first_index: usize,
+    lines: &[String],
+    policy: &SafetyPolicy,
+    max_removed: usize,
+) -> Result<SafeCounts> {
+    let mut counts = SafeCounts::default();
+
+    for (index, line) in lines.iter().enumerate() {
+        let levels = parse_report(first_index + index, line)?;
+
+        if report_is_safe(policy, &levels) {
+            counts.safe += 1;
+            counts.dampened += 1;
+        } else if report_is_safe_dampened(policy, &levels, max_removed) {
+            counts.dampened += 1;
+        }
+    }
+
+    Ok(counts)
+}
+
+/// Reads the lines of `reader` and sends them in numbered chunks until the
+/// input or the receivers run out.
+fn send_chunks(
+    reader: impl BufRead,
+    sender: SyncSender<(usize, Vec<String>)>,
+) -> std::io::Result<()> {
+    let mut lines = reader.lines();
+    let mut first_index = 0;
+
+    loop {
+        let chunk = lines
+            .by_ref()
+            .take(CHUNK_LINES)
+            .collect::<Result<Vec<_>, _>>()?;
+        if chunk.is_empty() {
+            return Ok(());
+        }
+
+        let len = chunk.len();
+        if sender.send((first_index, chunk)).is_err() {
+            return Ok(());
+        }
+        first_index += len;
+    }
+}
+
+/// Counts the safe reports of both parts in a single read of `reader`. The
+/// lines are split in chunks checked by `threads` worker threads while the
+/// next chunks are read. The totals do not depend on the scheduling, and the
+/// error reported is the first one in the input.
+pub fn safe_counts_from_reader(
+    reader: impl BufRead,
+    policy: &SafetyPolicy,
+    max_removed: usize,
+    threads: usize,
+) -> Result<SafeCounts> {
+    let threads = threads.max(1);
+    // Bounds the lines in memory while the workers catch up
+    let (sender, receiver) = sync_channel::<(usize, Vec<String>)>(threads * 2);
+    let receiver = Mutex::new(receiver);
+
+    let (read, mut chunks) = std::thread::scope(|scope| {
+        let workers = (0..threads)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut counts = Vec::new();
+                    loop {
+                        // Release the lock before counting
+                        let chunk = receiver.lock().unwrap().recv();
+                        let Ok((first_index, lines)) = chunk else {
+                            break;
+                        };
+
+                        let result = count_chunk(first_index, &lines, policy, max_removed);
+                        counts.push((first_index, result));
+                    }
+
+                    counts
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let read = send_chunks(reader, sender);
+
+        let chunks = workers
+            .into_iter()
+            .flat_map(|worker| worker.join().unwrap())
+            .collect::<Vec<_>>();
+
+        (read, chunks)
+    });
+
+    chunks.sort_by_key(|&(first_index, _)| first_index);
+
+    let mut counts = SafeCounts::default();
+    for (_, chunk) in chunks {
+        counts = counts.add(chunk?);
+    }
+    read?;
+
+    Ok(counts)
+}
+
+/// Counts the safe reports of both parts, reading the file once.
+pub fn safe_counts(
+    file_name: &str,
+    policy: &SafetyPolicy,
+    max_removed: usize,
+    threads: usize,
+) -> Result<SafeCounts> {
+    let file = std::fs::File::open(file_name)?;
+
+    safe_counts_from_reader(std::io::BufReader::new(file), policy, max_removed, threads)
+}
+
+pub struct Day2 {
+    reports: Vec<Vec<u32>>,
+    policy: SafetyPolicy,
+    max_removed: usize,
+}
+
+impl Day2 {
+    pub fn with_policy(self, policy: SafetyPolicy) -> Self {
+        Self { policy, ..self }
+    }
+
+    /// Lets the dampener of part 2 remove up to `max_removed` levels instead
+    /// of one.
+    pub fn with_max_removed(self, max_removed: usize) -> Self {
+        Self {
+            max_removed,
+            ..self
+        }
+    }
+
+    pub fn reports(&self) -> &[Vec<u32>] {
+        &self.reports
+    }
+
+    /// The verdict of every report, with the policy and dampening of this
+    /// solution.
+    pub fn verdicts(&self) -> impl Iterator<Item = Verdict> + '_ {
+        self.reports
+            .iter()
+            .map(|levels| verdict(&self.policy, levels, self.max_removed))
+    }
+}
+
+impl Solution for Day2 {
+    const DAY: u32 = 2;
+
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self> {
+        let reports = input
+            .lines()
+            .enumerate()
+            .map(|(index, line)| parse_report(index, line))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            reports,
+            policy: SafetyPolicy::default(),
+            max_removed: 1,
+        })
+    }
+
+    fn part1(&self) -> Result<u64> {
+        Ok(self
+            .reports
+            .iter()
+            .filter(|levels| report_is_safe(&self.policy, levels))
+            .count() as u64)
+    }
+
+    fn part2(&self) -> Result<u64> {
+        Ok(self
+            .reports
+            .iter()
+            .filter(|levels| report_is_safe_dampened(&self.policy, levels, self.max_removed))
+            .count() as u64)
+    }
+}
@@ -0,0 +1,545 @@
+use std::{fmt::Display, io::BufRead};
+
+use anyhow::{bail, Context, Result};
+
+use crate::{parse::ParseError, Solution};
+
+/// An instruction found in the corrupted memory.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Instruction {
+    /// `mul(a,b)`
+    Mul(u64, u64),
+    /// `do()`
+    Do,
+    /// `don't()`
+    Dont,
+}
+
+/// An instruction and the byte offset where it starts in the memory.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Token {
+    pub offset: usize,
+    pub instruction: Instruction,
+}
+
+/// What the memory at some offset starts with.
+enum Scan {
+    /// An instruction of `len` bytes.
+    Instruction(Instruction, usize),
+    /// An operand at `start`, `len` bytes long, that does not fit in a `u64`.
+    Overflow {
+        start: usize,
+        len: usize,
+    },
+    /// The memory ends before telling whether an instruction starts here.
+    Incomplete,
+    None,
+}
+
+/// The value and length of the decimal number `memory` starts with, if any.
+/// The value is `None` if it does not fit in a `u64`.
+fn scan_operand(memory: &[u8]) -> Option<(Option<u64>, usize)> {
+    let len = memory.iter().take_while(|c| c.is_ascii_digit()).count();
+    if len == 0 {
+        return None;
+    }
+
+    let value = memory[..len].iter().try_fold(0u64, |value, &c| {
+        value.checked_mul(10)?.checked_add(u64::from(c - b'0'))
+    });
+
+    Some((value, len))
+}
+
+/// Most digits of an operand in strict mode.
+const STRICT_DIGITS: usize = 3;
+
+fn scan(memory: &[u8], strict: bool) -> Scan {
+    let mut incomplete = false;
+
+    for (keyword, instruction) in [
+        (&b"do()"[..], Instruction::Do),
+        (b"don't()", Instruction::Dont),
+    ] {
+        if memory.starts_with(keyword) {
+            return Scan::Instruction(instruction, keyword.len());
+        }
+        incomplete |= keyword.starts_with(memory);
+    }
+
+    if !memory.starts_with(b"mul(") {
+        return if incomplete || b"mul(".starts_with(memory) {
+            Scan::Incomplete
+        } else {
+            Scan::None
+        };
+    }
+
+    let mut len = 4;
+    let mut operands = [0; 2];
+    for (index, separator) in [b',', b')'].into_iter().enumerate() {
+        let Some((value, digits)) = scan_operand(&memory[len..]) else {
+            return if len == memory.len() {
+                Scan::Incomplete
+            } else {
+                Scan::None
+            };
+        };
+        match memory.get(len + digits) {
+            Some(&c) if c == separator => {}
+            Some(_) => return Scan::None,
+            None => return Scan::Incomplete,
+        }
+        if strict && digits > STRICT_DIGITS {
+            return Scan::None;
+        }
+        let Some(value) = value else {
+            return Scan::Overflow {
+                start: len,
+                len: digits,
+            };
+        };
+
+        operands[index] = value;
+        len += digits + 1;
+    }
+
+    Scan::Instruction(Instruction::Mul(operands[0], operands[1]), len)
+}
+
+/// Why a `mul` near miss is not an instruction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Rejection {
+    /// `found` cannot continue the instruction.
+    Unexpected {
+        found: char,
+        expected: &'static str,
+    },
+    MissingOperand,
+    Whitespace,
+    /// An operand is longer than strict mode allows.
+    TooManyDigits,
+    /// An operand does not fit in a `u64`.
+    OperandTooLarge,
+    /// The memory ends inside the instruction.
+    Unterminated,
+}
+
+impl Display for Rejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unexpected { found, expected } => {
+                write!(f, "unexpected {found:?}, expected {expected}")
+            }
+            Self::MissingOperand => write!(f, "missing operand"),
+            Self::Whitespace => write!(f, "whitespace inside the instruction"),
+            Self::TooManyDigits => write!(f, "operand has more than {STRICT_DIGITS} digits"),
+            Self::OperandTooLarge => write!(f, "operand is too large"),
+            Self::Unterminated => write!(f, "memory ends inside the instruction"),
+        }
+    }
+}
+
+/// Something that looks like a `mul` instruction but is not one.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NearMiss {
+    pub offset: usize,
+    pub line: usize,
+    pub column: usize,
+    /// The memory from the start of the near miss up to where it was
+    /// rejected.
+    pub text: String,
+    pub rejection: Rejection,
+}
+
+enum NearMissScan {
+    /// A near miss of `len` bytes.
+    Found(usize, Rejection),
+    Incomplete,
+    None,
+}
+
+/// Matches memory that [`scan`] rejected against a lenient `mul` grammar,
+/// which allows spaces and missing, long or oversized operands, to tell why
+/// it is not an instruction.
+fn scan_near_miss(memory: &[u8], strict: bool, end: bool) -> NearMissScan {
+    if !memory.starts_with(b"mul") {
+        return NearMissScan::None;
+    }
+
+    let mut len = 3;
+    let mut rejection = None;
+    for (separator, operand, expected) in [
+        (b'(', false, "`(`"),
+        (b',', true, "a digit or `,`"),
+        (b')', true, "a digit or `)`"),
+    ] {
+        let mut spaces = memory[len..].iter().take_while(|&&c| c == b' ').count();
+        let digits = if operand {
+            memory[len + spaces..]
+                .iter()
+                .take_while(|c| c.is_ascii_digit())
+                .count()
+        } else {
+            0
+        };
+        let too_large = operand
+            && scan_operand(&memory[len + spaces..]).is_some_and(|(value, _)| value.is_none());
+        if digits > 0 {
+            spaces += memory[len + spaces + digits..]
+                .iter()
+                .take_while(|&&c| c == b' ')
+                .count();
+        }
+        len += spaces + digits;
+
+        let Some(&c) = memory.get(len) else {
+            return if end {
+                NearMissScan::Found(len, Rejection::Unterminated)
+            } else {
+                NearMissScan::Incomplete
+            };
+        };
+        if c != separator {
+            let found = if c.is_ascii() { c as char } else { '\u{fffd}' };
+            return NearMissScan::Found(len + 1, Rejection::Unexpected { found, expected });
+        }
+        len += 1;
+
+        // Keep the first reason in reading order
+        if operand && digits == 0 {
+            rejection.get_or_insert(Rejection::MissingOperand);
+        } else if spaces > 0 {
+            rejection.get_or_insert(Rejection::Whitespace);
+        } else if strict && digits > STRICT_DIGITS {
+            rejection.get_or_insert(Rejection::TooManyDigits);
+        } else if too_large {
+            rejection.get_or_insert(Rejection::OperandTooLarge);
+        }
+    }
+
+    match rejection {
+        Some(rejection) => NearMissScan::Found(len, rejection),
+        // A valid instruction, not a near miss
+        None => NearMissScan::None,
+    }
+}
+
+/// An instruction, or a near miss when they are reported.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Lexeme {
+    Instruction(Token),
+    NearMiss(NearMiss),
+}
+
+/// Finds the instructions hidden in the corrupted memory, skipping anything
+/// else. The memory is read as it is scanned, and only the bytes of an
+/// instruction that may continue in the next read are kept, so its size does
+/// not matter.
+pub struct Lexer<R> {
+    reader: R,
+    /// Bytes read, scanned up to `start`.
+    buffer: Vec<u8>,
+    start: usize,
+    /// Offset in the memory of the byte at `start`.
+    offset: usize,
+    /// Line and column of the byte at `start`.
+    line: usize,
+    column: usize,
+    end: bool,
+    strict: bool,
+    near_misses: bool,
+}
+
+impl<R: BufRead> Lexer<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            buffer: Vec::new(),
+            start: 0,
+            offset: 0,
+            line: 1,
+            column: 1,
+            end: false,
+            strict: false,
+            near_misses: false,
+        }
+    }
+
+    /// In strict mode, operands have at most 3 digits.
+    pub fn strict(self, strict: bool) -> Self {
+        Self { strict, ..self }
+    }
+
+    /// Also reports the near misses among the rejected memory.
+    pub fn lexemes(self) -> Lexemes<R> {
+        Lexemes(Self {
+            near_misses: true,
+            ..self
+        })
+    }
+
+    fn unscanned(&self) -> &[u8] {
+        &self.buffer[self.start..]
+    }
+
+    /// Moves past `len` scanned bytes.
+    fn advance(&mut self, len: usize) {
+        for &c in &self.buffer[self.start..self.start + len] {
+            if c == b'\n' {
+                self.line += 1;
+                self.column = 1;
+            } else if c & 0xc0 != 0x80 {
+                // Count characters, not UTF-8 continuation bytes
+                self.column += 1;
+            }
+        }
+
+        self.start += len;
+        self.offset += len;
+    }
+
+    /// Reads more of the memory into the buffer, after the bytes not scanned
+    /// yet.
+    fn fill(&mut self) -> std::io::Result<()> {
+        self.buffer.drain(..self.start);
+        self.start = 0;
+
+        let bytes = self.reader.fill_buf()?;
+        if bytes.is_empty() {
+            self.end = true;
+        }
+
+        let len = bytes.len();
+        self.buffer.extend_from_slice(bytes);
+        self.reader.consume(len);
+
+        Ok(())
+    }
+
+    fn next_lexeme(&mut self) -> Result<Option<Lexeme>> {
+        loop {
+            // Skip to the last byte that may start an instruction
+            let mut skipped = 0;
+            while skipped < self.unscanned().len() {
+                let memory = &self.unscanned()[skipped..];
+                match scan(memory, self.strict) {
+                    Scan::Instruction(instruction, len) => {
+                        self.advance(skipped);
+                        let token = Token {
+                            offset: self.offset,
+                            instruction,
+                        };
+                        self.advance(len);
+
+                        return Ok(Some(Lexeme::Instruction(token)));
+                    }
+                    Scan::Overflow { start, len } if !self.near_misses => {
+                        self.advance(skipped);
+                        let text = String::from_utf8_lossy(&self.unscanned()[start..start + len]);
+                        // Operands are ASCII digits after `mul(`
+                        bail!(ParseError::new(
+                            Day3::DAY,
+                            self.line,
+                            self.column + start,
+                            text,
+                            "invalid operand"
+                        ));
+                    }
+                    Scan::Incomplete if !self.end => break,
+                    Scan::Incomplete | Scan::None if !self.near_misses => skipped += 1,
+                    Scan::Incomplete | Scan::None | Scan::Overflow { .. } => {
+                        match scan_near_miss(memory, self.strict, self.end) {
+                            NearMissScan::Found(len, rejection) => {
+                                self.advance(skipped);
+                                let text = &self.unscanned()[..len];
+                                let near_miss = NearMiss {
+                                    offset: self.offset,
+                                    line: self.line,
+                                    column: self.column,
+                                    text: String::from_utf8_lossy(text).into_owned(),
+                                    rejection,
+                                };
+                                // Instructions may start inside the near miss
+                                self.advance(1);
+
+                                return Ok(Some(Lexeme::NearMiss(near_miss)));
+                            }
+                            NearMissScan::Incomplete => break,
+                            NearMissScan::None => skipped += 1,
+                        }
+                    }
+                }
+            }
+            self.advance(skipped);
+
+            if self.end {
+                return Ok(None);
+            }
+            self.fill()?;
+        }
+    }
+
+    fn next_item(&mut self) -> Option<Result<Lexeme>> {
+        if self.end && self.unscanned().is_empty() {
+            return None;
+        }
+
+        let lexeme = self.next_lexeme().transpose();
+        if matches!(lexeme, Some(Err(_))) {
+            // Stop after an error
+            self.end = true;
+            self.buffer.clear();
+            self.start = 0;
+        }
+
+        lexeme
+    }
+}
+
+impl<R: BufRead> Iterator for Lexer<R> {
+    type Item = Result<Token>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.next_item()? {
+            Ok(Lexeme::Instruction(token)) => Some(Ok(token)),
+            Ok(Lexeme::NearMiss(_)) => unreachable!("near misses are only reported by lexemes()"),
+            Err(err) => Some(Err(err)),
+        }
+    }
+}
+
+/// The instructions and near misses of a [`Lexer`].
+pub struct Lexemes<R>(Lexer<R>);
+
+impl<R: BufRead> Iterator for Lexemes<R> {
+    type Item = Result<Lexeme>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next_item()
+    }
+}
+
+/// The state of the interpreter between two instructions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Machine {
+    /// Whether `do()` and `don't()` are obeyed.
+    pub conditions: bool,
+    /// Whether `mul` instructions are executed.
+    pub enabled: bool,
+    /// Sum of the executed multiplications.
+    pub sum: u64,
+}
+
+impl Machine {
+    pub fn new(conditions: bool) -> Self {
+        Self {
+            conditions,
+            enabled: true,
+            sum: 0,
+        }
+    }
+
+    /// Fails when a multiplication overflows the sum.
+    pub fn execute(&mut self, instruction: Instruction) -> Result<()> {
+        match instruction {
+            Instruction::Mul(a, b) => {
+                if self.enabled {
+                    self.sum = a
+                        .checked_mul(b)
+                        .and_then(|product| self.sum.checked_add(product))
+                        .with_context(|| format!("mul({a},{b}) overflows the sum"))?;
+                }
+            }
+            Instruction::Do => {
+                if self.conditions {
+                    self.enabled = true;
+                }
+            }
+            Instruction::Dont => {
+                if self.conditions {
+                    self.enabled = false;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Runs every instruction of `memory` and returns the sum of the
+/// multiplications.
+pub fn interpret(memory: impl BufRead, conditions: bool) -> Result<u64> {
+    execute(Lexer::new(memory), conditions)
+}
+
+/// Runs the instructions of a lexer, strict or not, and returns the sum of
+/// the multiplications.
+pub fn execute(tokens: impl Iterator<Item = Result<Token>>, conditions: bool) -> Result<u64> {
+    let mut machine = Machine::new(conditions);
+
+    for token in tokens {
+        machine.execute(token?.instruction)?;
+    }
+
+    Ok(machine.sum)
+}
+
+pub fn sanitized_mult_from_reader(reader: impl BufRead) -> Result<u64> {
+    interpret(reader, false)
+}
+
+pub fn sanitized_mult_with_conditions_from_reader(reader: impl BufRead) -> Result<u64> {
+    interpret(reader, true)
+}
+
+pub fn sanitized_mult(file_name: &str) -> Result<u64> {
+    let file = std::fs::File::open(file_name)?;
+
+    sanitized_mult_from_reader(std::io::BufReader::new(file))
+}
+
+pub fn sanitized_mult_with_conditions(file_name: &str) -> Result<u64> {
+    let file = std::fs::File::open(file_name)?;
+
+    sanitized_mult_with_conditions_from_reader(std::io::BufReader::new(file))
+}
+
+pub struct Day3 {
+    tokens: Vec<Token>,
+}
+
+impl Day3 {
+    /// Parses the memory, only accepting operands of 1 to 3 digits, as the
+    /// puzzle states, if `strict`.
+    pub fn parse_strict(input: &str, strict: bool) -> Result<Self> {
+        let tokens = Lexer::new(input.as_bytes())
+            .strict(strict)
+            .collect::<Result<_>>()?;
+
+        Ok(Self { tokens })
+    }
+
+    fn execute(&self, conditions: bool) -> Result<u64> {
+        execute(self.tokens.iter().copied().map(Ok), conditions)
+    }
+}
+
+impl Solution for Day3 {
+    const DAY: u32 = 3;
+
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self> {
+        Self::parse_strict(input, false)
+    }
+
+    fn part1(&self) -> Result<u64> {
+        self.execute(false)
+    }
+
+    fn part2(&self) -> Result<u64> {
+        self.execute(true)
+    }
+}
@@ -0,0 +1,135 @@
+use std::io::BufRead;
+
+use anyhow::Result;
+
+use crate::{
+    grid::{Direction, Grid},
+    Solution,
+};
+
+fn find_all(s: &[char], pattern: &[char]) -> u64 {
+    let mut count = 0;
+
+    for window in s.windows(pattern.len()) {
+        if window == pattern {
+            count += 1;
+        }
+    }
+
+    let rev_pattern = pattern.iter().rev().copied().collect::<Vec<_>>();
+
+    for window in s.windows(rev_pattern.len()) {
+        if window == rev_pattern {
+            count += 1;
+        }
+    }
+
+    count
+}
+
+fn parse_puzzle(reader: impl BufRead) -> Result<Grid<char>> {
+    Grid::from_reader(reader, Day4::DAY, |_, _, c| Ok(c))
+}
+
+fn count_1d(puzzle: &Grid<char>) -> u64 {
+    const PATTERN: &[char] = &['X', 'M', 'A', 'S'];
+
+    // Search for "XMAS" in the rows, the columns and the diagonals
+    [
+        Direction::Right,
+        Direction::Down,
+        Direction::DownRight,
+        Direction::DownLeft,
+    ]
+    .into_iter()
+    .flat_map(|direction| puzzle.lines(direction))
+    .map(|line| find_all(&line.copied().collect::<Vec<_>>(), PATTERN))
+    .sum()
+}
+
+fn count_2d(puzzle: &Grid<char>) -> u64 {
+    const PATTERN: &[char] = &['M', 'A', 'S'];
+
+    let mut count = 0;
+
+    let width = puzzle.width();
+    let height = puzzle.height();
+
+    let rev_pattern = PATTERN.iter().rev().copied().collect::<Vec<_>>();
+
+    for i in 0..height {
+        if i + PATTERN.len() > height {
+            break;
+        }
+
+        for j in 0..width {
+            if j + PATTERN.len() > width {
+                break;
+            }
+
+            let diagonal1 = puzzle
+                .line(j, i, Direction::DownRight)
+                .take(PATTERN.len())
+                .copied()
+                .collect::<Vec<_>>();
+            let diagonal2 = puzzle
+                .line(j + PATTERN.len() - 1, i, Direction::DownLeft)
+                .take(PATTERN.len())
+                .copied()
+                .collect::<Vec<_>>();
+
+            if (diagonal1 == PATTERN || diagonal1 == rev_pattern)
+                && (diagonal2 == PATTERN || diagonal2 == rev_pattern)
+            {
+                count += 1;
+            }
+        }
+    }
+
+    count
+}
+
+pub fn xmas_count_1d_from_reader(reader: impl BufRead) -> Result<u64> {
+    Ok(count_1d(&parse_puzzle(reader)?))
+}
+
+pub fn xmas_count_2d_from_reader(reader: impl BufRead) -> Result<u64> {
+    Ok(count_2d(&parse_puzzle(reader)?))
+}
+
+pub fn xmas_count_1d(file_name: &str) -> Result<u64> {
+    let file = std::fs::File::open(file_name)?;
+
+    xmas_count_1d_from_reader(std::io::BufReader::new(file))
+}
+
+pub fn xmas_count_2d(file_name: &str) -> Result<u64> {
+    let file = std::fs::File::open(file_name)?;
+
+    xmas_count_2d_from_reader(std::io::BufReader::new(file))
+}
+
+pub struct Day4 {
+    puzzle: Grid<char>,
+}
+
+impl Solution for Day4 {
+    const DAY: u32 = 4;
+
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self> {
+        Ok(Self {
+            puzzle: parse_puzzle(input.as_bytes())?,
+        })
+    }
+
+    fn part1(&self) -> Result<u64> {
+        Ok(count_1d(&self.puzzle))
+    }
+
+    fn part2(&self) -> Result<u64> {
+        Ok(count_2d(&self.puzzle))
+    }
+}
@@ -0,0 +1,229 @@
+use std::{collections::HashSet, io::BufRead};
+
+use anyhow::{bail, Result};
+use petgraph::prelude::DiGraphMap;
+
+use crate::{
+    parse::{number, split, ParseError},
+    Solution,
+};
+
+struct OrderingRules {
+    graph: DiGraphMap<u64, u64>,
+}
+
+impl OrderingRules {
+    fn new() -> Self {
+        Self {
+            graph: DiGraphMap::new(),
+        }
+    }
+
+    fn add_rule(&mut self, before: u64, after: u64) {
+        self.graph.add_edge(before, after, 1);
+    }
+
+    fn is_valid_partial(&self, page_before: u64, next_pages: &[u64]) -> bool {
+        let mut valid = true;
+        for page_after in next_pages {
+            if self.graph.contains_edge(*page_after, page_before) {
+                valid = false;
+            }
+        }
+
+        valid
+    }
+
+    fn is_valid(&self, pages: &[u64]) -> bool {
+        let mut valid = true;
+        for (index, page_before) in pages.iter().take(pages.len().saturating_sub(1)).enumerate() {
+            let next_pages = &pages[index + 1..];
+
+            if !self.is_valid_partial(*page_before, next_pages) {
+                valid = false;
+            }
+        }
+
+        valid
+    }
+
+    // Based on https://en.wikipedia.org/wiki/Topological_sorting#Depth-first_search
+    fn topological_sort(&self, nodes: &[u64]) -> Vec<u64> {
+        let mut sorted = Vec::new();
+        let visitable = nodes.iter().cloned().collect::<HashSet<_>>();
+        let mut visited = HashSet::new();
+
+        for node in nodes {
+            if !visited.contains(node) {
+                self.visit(*node, &visitable, &mut visited, &mut sorted);
+            }
+        }
+
+        sorted.reverse();
+        sorted
+    }
+
+    fn visit(
+        &self,
+        node: u64,
+        nodes: &HashSet<u64>,
+        visited: &mut HashSet<u64>,
+        sorted: &mut Vec<u64>,
+    ) {
+        visited.insert(node);
+
+        for neighbor in self.graph.neighbors(node) {
+            if !nodes.contains(&neighbor) {
+                // Skip nodes that are not in the original list
+                continue;
+            }
+
+            if !visited.contains(&neighbor) {
+                self.visit(neighbor, nodes, visited, sorted);
+            }
+        }
+
+        sorted.push(node);
+    }
+}
+
+/// Parses the page number in `field` of the line at `index`.
+fn page(index: usize, column: usize, field: &str) -> Result<u64, ParseError> {
+    if field.trim().is_empty() {
+        return Err(ParseError::new(
+            Day5::DAY,
+            index + 1,
+            column,
+            field,
+            "missing page number",
+        ));
+    }
+
+    number(Day5::DAY, index + 1, column, field)
+}
+
+fn parse_input(reader: impl BufRead) -> Result<(OrderingRules, Vec<Vec<u64>>)> {
+    let mut ordering_rules = OrderingRules::new();
+
+    let mut lines = reader.lines().enumerate();
+    for (index, line) in lines.by_ref() {
+        let line = line?;
+
+        if line.is_empty() {
+            break;
+        }
+
+        let mut pages = split(&line, |c| c == '|');
+        let (Some((column_before, before)), Some((column_after, after)), None) =
+            (pages.next(), pages.next(), pages.next())
+        else {
+            bail!(ParseError::new(
+                Day5::DAY,
+                index + 1,
+                1,
+                line.as_str(),
+                "expected an ordering rule"
+            ));
+        };
+        let before = page(index, column_before, before)?;
+        let after = page(index, column_after, after)?;
+
+        ordering_rules.add_rule(before, after);
+    }
+
+    let mut updates = Vec::new();
+
+    for (index, line) in lines {
+        let line = line?;
+
+        if line.is_empty() {
+            continue;
+        }
+
+        let pages = split(&line, |c| c == ',')
+            .map(|(column, field)| page(index, column, field))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        updates.push(pages);
+    }
+
+    Ok((ordering_rules, updates))
+}
+
+fn valid_middle_page_sum(ordering_rules: &OrderingRules, updates: &[Vec<u64>]) -> u64 {
+    updates
+        .iter()
+        .filter(|pages| ordering_rules.is_valid(pages))
+        .map(|pages| pages[pages.len() / 2])
+        .sum()
+}
+
+fn reordered_middle_page_sum(ordering_rules: &OrderingRules, updates: &[Vec<u64>]) -> u64 {
+    let mut reordered_middle_page_sum = 0;
+
+    for pages in updates {
+        if ordering_rules.is_valid(pages) {
+            continue;
+        }
+
+        let pages = ordering_rules.topological_sort(pages);
+
+        assert!(
+            ordering_rules.is_valid(&pages),
+            "Reordered pages {:?} are not valid",
+            pages
+        );
+
+        let middle_page = pages[pages.len() / 2];
+        reordered_middle_page_sum += middle_page;
+    }
+
+    reordered_middle_page_sum
+}
+
+pub fn verify_updates(file_name: &str) -> Result<(u64, u64)> {
+    let file = std::fs::File::open(file_name)?;
+
+    verify_updates_from_reader(std::io::BufReader::new(file))
+}
+
+pub fn verify_updates_from_reader(reader: impl BufRead) -> Result<(u64, u64)> {
+    let (ordering_rules, updates) = parse_input(reader)?;
+
+    Ok((
+        valid_middle_page_sum(&ordering_rules, &updates),
+        reordered_middle_page_sum(&ordering_rules, &updates),
+    ))
+}
+
+pub struct Day5 {
+    ordering_rules: OrderingRules,
+    updates: Vec<Vec<u64>>,
+}
+
+impl Solution for Day5 {
+    const DAY: u32 = 5;
+
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self> {
+        let (ordering_rules, updates) = parse_input(input.as_bytes())?;
+
+        Ok(Self {
+            ordering_rules,
+            updates,
+        })
+    }
+
+    fn part1(&self) -> Result<u64> {
+        Ok(valid_middle_page_sum(&self.ordering_rules, &self.updates))
+    }
+
+    fn part2(&self) -> Result<u64> {
+        Ok(reordered_middle_page_sum(
+            &self.ordering_rules,
+            &self.updates,
+        ))
+    }
+}
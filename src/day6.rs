@@ -0,0 +1,295 @@
+use std::{fmt::Display, io::BufRead};
+
+use anyhow::{bail, Result};
+
+use crate::{
+    grid::{Direction, Grid},
+    parse::ParseError,
+    Solution,
+};
+
+fn direction_from_char(c: char) -> Option<Direction> {
+    match c {
+        '^' => Some(Direction::Up),
+        'v' => Some(Direction::Down),
+        '<' => Some(Direction::Left),
+        '>' => Some(Direction::Right),
+        _ => None,
+    }
+}
+
+#[derive(Clone)]
+struct Guard {
+    x: usize,
+    y: usize,
+    direction: Direction,
+}
+
+impl Display for Guard {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let c = match self.direction {
+            Direction::Up => '^',
+            Direction::Down => 'v',
+            Direction::Left => '<',
+            Direction::Right => '>',
+            _ => unreachable!("the guard only moves orthogonally"),
+        };
+
+        write!(f, "{c}")
+    }
+}
+
+/// Directions in which a position has been visited, one bit per direction.
+#[derive(Clone, Copy, Default)]
+struct Visited(u8);
+
+impl Visited {
+    fn visit(&mut self, direction: &Direction) {
+        self.0 |= 1 << *direction as u8;
+    }
+
+    fn is_visited(&self) -> bool {
+        self.0 != 0
+    }
+
+    fn has_direction(&self, direction: &Direction) -> bool {
+        self.0 & (1 << *direction as u8) != 0
+    }
+}
+
+#[derive(Clone)]
+pub struct Map {
+    obstacles: Grid<bool>,
+    potential_obstacle: Option<(usize, usize)>,
+    visited: Grid<Visited>,
+    guard: Guard,
+}
+
+enum Movement {
+    Moved(usize, usize),
+    HitObstacle,
+    OutOfMap,
+}
+
+impl Map {
+    pub fn from_file(file_name: &str) -> Result<Self> {
+        let file = std::fs::File::open(file_name);
+        let reader = std::io::BufReader::new(file?);
+
+        Self::from_reader(reader)
+    }
+
+    pub fn from_reader(reader: impl BufRead) -> Result<Self> {
+        let mut guard = None;
+
+        let obstacles = Grid::from_reader(reader, Day6::DAY, |x, y, c| match c {
+            '.' => Ok(false),
+            '#' => Ok(true),
+            _ => {
+                let direction = direction_from_char(c).ok_or("invalid tile")?;
+                if guard.is_some() {
+                    return Err("second guard".into());
+                }
+                guard = Some(Guard { x, y, direction });
+                Ok(false)
+            }
+        })?;
+
+        if obstacles.height() == 0 {
+            bail!(ParseError::new(Day6::DAY, 1, 1, "", "empty map"));
+        }
+        let Some(guard) = guard else {
+            bail!(ParseError::new(Day6::DAY, 1, 1, "", "no guard"));
+        };
+
+        let visited = obstacles.map(|_| Visited::default());
+
+        Ok(Self {
+            obstacles,
+            visited,
+            potential_obstacle: None,
+            guard,
+        })
+    }
+
+    fn can_move_from(&self, x: usize, y: usize, direction: Direction) -> Movement {
+        match self.obstacles.step(x, y, direction) {
+            None => Movement::OutOfMap,
+            Some((x, y)) if self.obstacles[(x, y)] => Movement::HitObstacle,
+            Some((x, y)) => Movement::Moved(x, y),
+        }
+    }
+
+    fn patrol_has_loop(&mut self) -> bool {
+        loop {
+            match self.can_move_from(self.guard.x, self.guard.y, self.guard.direction) {
+                Movement::Moved(x, y) => {
+                    if self.visited[(x, y)].has_direction(&self.guard.direction) {
+                        return true;
+                    }
+
+                    self.visited[(x, y)].visit(&self.guard.direction);
+
+                    self.guard.x = x;
+                    self.guard.y = y;
+                }
+                Movement::HitObstacle => {
+                    self.guard.direction = self.guard.direction.turn_right();
+                    self.visited[(self.guard.x, self.guard.y)].visit(&self.guard.direction);
+                }
+                Movement::OutOfMap => {
+                    return false;
+                }
+            }
+        }
+    }
+
+    fn distinct_positions(&mut self) -> u64 {
+        self.visited[(self.guard.x, self.guard.y)].visit(&self.guard.direction);
+
+        self.patrol_has_loop();
+
+        self.visited
+            .iter()
+            .filter(|(_, visited)| visited.is_visited())
+            .count() as u64
+    }
+
+    fn patrol(&mut self) -> (u64, u64) {
+        self.visited[(self.guard.x, self.guard.y)].visit(&self.guard.direction);
+
+        let mut visited_count = 1;
+        let mut loop_obstacles_count = 0;
+        let mut loop_obstacles = self.obstacles.map(|_| false);
+        let initial_x = self.guard.x;
+        let initial_y = self.guard.y;
+
+        loop {
+            // println!("{}", self);
+            // println!();
+
+            match self.can_move_from(self.guard.x, self.guard.y, self.guard.direction) {
+                Movement::Moved(x, y) => {
+                    // let prev_x = self.guard.x;
+                    // let prev_y = self.guard.y;
+
+                    if !self.obstacles[(x, y)]
+                        && !loop_obstacles[(x, y)]
+                        && (x, y) != (initial_x, initial_y)
+                        && !self.visited[(x, y)].is_visited()
+                    {
+                        let mut simulated_map = self.clone();
+                        simulated_map.obstacles[(x, y)] = true;
+
+                        if simulated_map.patrol_has_loop() {
+                            loop_obstacles[(x, y)] = true;
+                            loop_obstacles_count += 1;
+
+                            // let mut new_map = self.clone();
+                            // new_map.potential_obstacle = Some((x, y));
+
+                            // let rotated_direction = self.guard.direction.turn_right();
+
+                            // println!("Potential loop detected if placing an obstacle at ({x}, {y}) and then going {} from ({prev_x}, {prev_y}):", rotated_direction);
+                            // println!("{}", new_map);
+                            // println!();
+                        }
+                    }
+
+                    self.guard.x = x;
+                    self.guard.y = y;
+
+                    if !self.visited[(x, y)].is_visited() {
+                        visited_count += 1;
+                    }
+
+                    self.visited[(self.guard.x, self.guard.y)].visit(&self.guard.direction);
+                }
+                Movement::HitObstacle => {
+                    self.guard.direction = self.guard.direction.turn_right();
+                    self.visited[(self.guard.x, self.guard.y)].visit(&self.guard.direction);
+                }
+                Movement::OutOfMap => {
+                    break;
+                }
+            }
+        }
+
+        (visited_count, loop_obstacles_count)
+    }
+}
+
+impl Display for Map {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (y, row) in self.obstacles.rows().enumerate() {
+            for (x, &obstacle) in row.iter().enumerate() {
+                if self.guard.x == x && self.guard.y == y {
+                    write!(f, "{}", self.guard)?;
+                } else if obstacle {
+                    write!(f, "#")?;
+                } else if self.potential_obstacle == Some((x, y)) {
+                    write!(f, "O")?;
+                } else if self.visited[(x, y)].is_visited() {
+                    if (self.visited[(x, y)].has_direction(&Direction::Up)
+                        || self.visited[(x, y)].has_direction(&Direction::Down))
+                        && !(self.visited[(x, y)].has_direction(&Direction::Left)
+                            || self.visited[(x, y)].has_direction(&Direction::Right))
+                    {
+                        write!(f, "|")?;
+                    } else if (self.visited[(x, y)].has_direction(&Direction::Left)
+                        || self.visited[(x, y)].has_direction(&Direction::Right))
+                        && !(self.visited[(x, y)].has_direction(&Direction::Up)
+                            || self.visited[(x, y)].has_direction(&Direction::Down))
+                    {
+                        write!(f, "-")?;
+                    } else {
+                        write!(f, "+")?;
+                    }
+                } else {
+                    write!(f, ".")?;
+                }
+            }
+
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+pub fn calculate_guard_route(file_name: &str) -> Result<(u64, u64)> {
+    let mut map = Map::from_file(file_name)?;
+
+    Ok(map.patrol())
+}
+
+pub fn calculate_guard_route_from_reader(reader: impl BufRead) -> Result<(u64, u64)> {
+    let mut map = Map::from_reader(reader)?;
+
+    Ok(map.patrol())
+}
+
+pub struct Day6 {
+    map: Map,
+}
+
+impl Solution for Day6 {
+    const DAY: u32 = 6;
+
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self> {
+        Ok(Self {
+            map: Map::from_reader(input.as_bytes())?,
+        })
+    }
+
+    fn part1(&self) -> Result<u64> {
+        Ok(self.map.clone().distinct_positions())
+    }
+
+    fn part2(&self) -> Result<u64> {
+        Ok(self.map.clone().patrol().1)
+    }
+}
@@ -0,0 +1,194 @@
+use std::io::BufRead;
+
+use anyhow::{bail, Result};
+
+use crate::{parse::ParseError, Solution};
+
+#[derive(Debug)]
+pub struct Equation {
+    pub total: u64,
+    pub operands: Vec<u64>,
+}
+
+pub fn parse_input(file_name: &str) -> Result<Vec<Equation>> {
+    let file = std::fs::File::open(file_name);
+    let reader = std::io::BufReader::new(file?);
+
+    parse_equations(reader)
+}
+
+fn append_digit(number: u64, digit: u32) -> Option<u64> {
+    number.checked_mul(10)?.checked_add(u64::from(digit))
+}
+
+pub fn parse_equations(reader: impl BufRead) -> Result<Vec<Equation>> {
+    let mut equations = Vec::new();
+
+    for (index, line) in reader.lines().enumerate() {
+        let line = line?;
+
+        let error = |column: usize, text: &str, reason: &str| {
+            ParseError::new(Day7::DAY, index + 1, column + 1, text, reason)
+        };
+        let end_of_line = || error(line.chars().count(), "", "unexpected end of line");
+
+        let mut equation = line.chars().enumerate();
+
+        let mut total: u64 = 0;
+
+        while let Some((column, c)) = equation.next() {
+            if let Some(digit) = c.to_digit(10) {
+                total = append_digit(total, digit)
+                    .ok_or_else(|| error(column, &line, "number is too large"))?;
+            } else if c == ':' {
+                match equation.next() {
+                    Some((_, ' ')) => break,
+                    Some((column, c)) => {
+                        bail!(error(column, &c.to_string(), "unexpected character"))
+                    }
+                    None => bail!(end_of_line()),
+                }
+            } else {
+                bail!(error(column, &c.to_string(), "unexpected character"));
+            }
+        }
+
+        let mut operands = Vec::new();
+        let mut operand = None;
+
+        for (column, c) in equation {
+            if let Some(digit) = c.to_digit(10) {
+                operand = Some(
+                    append_digit(operand.unwrap_or_default(), digit)
+                        .ok_or_else(|| error(column, &line, "number is too large"))?,
+                );
+            } else if c == ' ' {
+                operands.push(operand.ok_or_else(|| error(column, " ", "unexpected space"))?);
+                operand = None;
+            } else {
+                bail!(error(column, &c.to_string(), "unexpected character"));
+            }
+        }
+
+        operands.push(operand.ok_or_else(end_of_line)?);
+
+        equations.push(Equation { total, operands });
+    }
+
+    Ok(equations)
+}
+
+fn concat(a: u64, b: u64) -> u64 {
+    // print!("{a} || {b} = ");
+
+    let mut a = a;
+    let mut new_b = b;
+
+    while new_b > 0 {
+        a *= 10;
+        new_b /= 10;
+    }
+
+    // println!("{}", a + b);
+
+    a + b
+}
+
+fn is_valid_operands(expected: u64, partial: u64, operands: &[u64]) -> bool {
+    if operands.is_empty() {
+        return expected == partial;
+    }
+
+    let operand = operands[0];
+    let rest = &operands[1..];
+
+    is_valid_operands(expected, partial + operand, rest)
+        || is_valid_operands(expected, partial * operand, rest)
+}
+
+fn is_valid_equation(equation: &Equation) -> bool {
+    let operand = equation.operands[0];
+    let rest = &equation.operands[1..];
+
+    is_valid_operands(equation.total, operand, rest)
+}
+
+fn is_valid_operands_with_concat(expected: u64, partial: u64, operands: &[u64]) -> bool {
+    if operands.is_empty() {
+        return expected == partial;
+    }
+
+    let operand = operands[0];
+    let rest = &operands[1..];
+
+    is_valid_operands_with_concat(expected, partial + operand, rest)
+        || is_valid_operands_with_concat(expected, partial * operand, rest)
+        || is_valid_operands_with_concat(expected, concat(partial, operand), rest)
+}
+
+fn is_valid_equation_with_concat(equation: &Equation) -> bool {
+    let operand = equation.operands[0];
+    let rest = &equation.operands[1..];
+
+    is_valid_operands_with_concat(equation.total, operand, rest)
+}
+
+pub fn fix_equations(file_name: &str) -> Result<(u64, u64)> {
+    let file = std::fs::File::open(file_name)?;
+
+    fix_equations_from_reader(std::io::BufReader::new(file))
+}
+
+pub fn fix_equations_from_reader(reader: impl BufRead) -> Result<(u64, u64)> {
+    let mut valid_equations_sum = 0;
+    let mut valid_equations_with_concat_sum = 0;
+
+    let equations = parse_equations(reader)?;
+
+    for equation in equations {
+        if is_valid_equation(&equation) {
+            valid_equations_sum += equation.total;
+        }
+
+        if is_valid_equation_with_concat(&equation) {
+            valid_equations_with_concat_sum += equation.total;
+        }
+    }
+
+    Ok((valid_equations_sum, valid_equations_with_concat_sum))
+}
+
+pub struct Day7 {
+    equations: Vec<Equation>,
+}
+
+impl Solution for Day7 {
+    const DAY: u32 = 7;
+
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self> {
+        let equations = parse_equations(input.as_bytes())?;
+
+        Ok(Self { equations })
+    }
+
+    fn part1(&self) -> Result<u64> {
+        Ok(self
+            .equations
+            .iter()
+            .filter(|equation| is_valid_equation(equation))
+            .map(|equation| equation.total)
+            .sum())
+    }
+
+    fn part2(&self) -> Result<u64> {
+        Ok(self
+            .equations
+            .iter()
+            .filter(|equation| is_valid_equation_with_concat(equation))
+            .map(|equation| equation.total)
+            .sum())
+    }
+}
@@ -0,0 +1,193 @@
+use std::collections::{HashMap, HashSet};
+use std::io::BufRead;
+
+use anyhow::Result;
+
+use crate::{grid::Grid, Solution};
+
+#[derive(Debug)]
+struct Vector {
+    x: i64,
+    y: i64,
+}
+
+#[derive(Clone, Eq, PartialEq, Hash)]
+struct Location {
+    x: i64,
+    y: i64,
+}
+
+impl Location {
+    fn add(&self, vector: &Vector) -> Self {
+        Self {
+            x: self.x + vector.x,
+            y: self.y + vector.y,
+        }
+    }
+
+    fn sub(&self, location: &Location) -> Vector {
+        Vector {
+            x: self.x - location.x,
+            y: self.y - location.y,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Map {
+    antennas: HashMap<char, Vec<Location>>,
+    tiles: Grid<char>,
+}
+
+impl Map {
+    pub fn from_file(file_name: &str) -> Result<Map> {
+        let file = std::fs::File::open(file_name);
+        let reader = std::io::BufReader::new(file?);
+
+        Self::from_reader(reader)
+    }
+
+    pub fn from_reader(reader: impl BufRead) -> Result<Map> {
+        let tiles = Grid::from_reader(reader, Day8::DAY, |_, _, c| Ok(c))?;
+
+        let mut antennas = HashMap::new();
+
+        tiles
+            .iter()
+            .filter(|(_, c)| **c != '.')
+            .try_for_each(|((col, row), c)| {
+                antennas.entry(*c).or_insert_with(Vec::new).push(Location {
+                    x: i64::try_from(col)?,
+                    y: i64::try_from(row)?,
+                });
+                Result::<(), anyhow::Error>::Ok(())
+            })?;
+
+        Ok(Map { antennas, tiles })
+    }
+
+    fn is_valid_antinode(&self, location: &Location) -> bool {
+        self.tiles.position(location.x, location.y).is_some()
+    }
+
+    fn antinodes(&self, antenna_a: &Location, antenna_b: &Location) -> Vec<Location> {
+        let vector = antenna_a.sub(antenna_b);
+
+        let mut antinodes = Vec::new();
+
+        let mut antenna_a = antenna_a.clone();
+
+        loop {
+            antenna_a = antenna_a.add(&vector);
+
+            if !self.is_valid_antinode(&antenna_a) {
+                break;
+            }
+
+            antinodes.push(antenna_a.clone());
+        }
+
+        antinodes
+    }
+}
+
+struct Pairs<'a, T> {
+    elements: &'a [T],
+    index: usize,
+    subindex: usize,
+}
+
+impl<'a, T> Pairs<'a, T> {
+    fn new(elements: &'a [T]) -> Self {
+        Self {
+            elements,
+            index: 0,
+            subindex: 1,
+        }
+    }
+}
+
+impl<'a, T> Iterator for Pairs<'a, T> {
+    type Item = (&'a T, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // If only one item is left
+        if self.index + 1 >= self.elements.len() {
+            return None;
+        }
+
+        let pair = (&self.elements[self.index], &self.elements[self.subindex]);
+
+        self.subindex += 1;
+
+        if self.subindex >= self.elements.len() {
+            self.index += 1;
+            self.subindex = self.index + 1;
+        }
+
+        Some(pair)
+    }
+}
+
+/// Counts the antinodes of every pair of antennas of the same frequency:
+/// only the nearest ones, or with `extended`, every one in line with the pair
+/// and the antennas themselves.
+fn count_antinodes(map: &Map, extended: bool) -> usize {
+    let mut antinodes = HashSet::<Location>::new();
+    if extended {
+        antinodes.extend(map.antennas.values().flatten().cloned());
+    }
+
+    for antennas in map.antennas.values() {
+        for (antenna_a, antenna_b) in Pairs::new(antennas) {
+            for (from, to) in [(antenna_a, antenna_b), (antenna_b, antenna_a)] {
+                let line = map.antinodes(from, to);
+
+                if extended {
+                    antinodes.extend(line);
+                } else {
+                    antinodes.extend(line.first().cloned());
+                }
+            }
+        }
+    }
+
+    antinodes.len()
+}
+
+pub fn detect_antinodes(file_name: &str) -> Result<(usize, usize)> {
+    let map = Map::from_file(file_name)?;
+
+    Ok((count_antinodes(&map, false), count_antinodes(&map, true)))
+}
+
+pub fn detect_antinodes_from_reader(reader: impl BufRead) -> Result<(usize, usize)> {
+    let map = Map::from_reader(reader)?;
+
+    Ok((count_antinodes(&map, false), count_antinodes(&map, true)))
+}
+
+pub struct Day8 {
+    map: Map,
+}
+
+impl Solution for Day8 {
+    const DAY: u32 = 8;
+
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self> {
+        Ok(Self {
+            map: Map::from_reader(input.as_bytes())?,
+        })
+    }
+
+    fn part1(&self) -> Result<usize> {
+        Ok(count_antinodes(&self.map, false))
+    }
+
+    fn part2(&self) -> Result<usize> {
+        Ok(count_antinodes(&self.map, true))
+    }
+}
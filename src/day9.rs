@@ -0,0 +1,171 @@
+use std::io::BufRead;
+
+use anyhow::{bail, Result};
+
+use crate::{parse::ParseError, Solution};
+
+#[derive(Clone, PartialEq)]
+enum Block {
+    Some { id: usize },
+    None,
+}
+
+#[derive(Clone)]
+pub struct Disk {
+    blocks: Vec<Block>,
+    size: usize,
+}
+
+impl Disk {
+    pub fn from_file(file_name: &str) -> Result<Self> {
+        let file = std::fs::File::open(file_name);
+        let reader = std::io::BufReader::new(file?);
+
+        Self::from_reader(reader)
+    }
+
+    pub fn from_reader(reader: impl BufRead) -> Result<Self> {
+        let mut blocks = Vec::new();
+
+        // Let's assume 1 byte per digit
+        let mut bytes = reader.bytes();
+        let mut is_free_space = false;
+        let mut file_id = 0;
+        let mut size = 0;
+
+        let mut column = 0;
+
+        while let Some(digit) = bytes.next() {
+            let digit = digit?;
+            column += 1;
+
+            if digit == b'\n' {
+                if let Some(next) = bytes.next() {
+                    let next = char::from(next?);
+                    bail!(ParseError::new(
+                        Day9::DAY,
+                        2,
+                        1,
+                        next,
+                        "expected end of input"
+                    ));
+                }
+                break;
+            }
+
+            let digit = match digit {
+                b'0'..=b'9' => digit - b'0',
+                _ => bail!(ParseError::new(
+                    Day9::DAY,
+                    1,
+                    column,
+                    char::from(digit),
+                    "invalid digit"
+                )),
+            };
+
+            if !is_free_space && digit == 0 {
+                bail!(ParseError::new(
+                    Day9::DAY,
+                    1,
+                    column,
+                    "0",
+                    "invalid file size"
+                ));
+            }
+
+            if !(is_free_space && digit == 0) {
+                for _ in 0..digit {
+                    blocks.push(if !is_free_space {
+                        Block::Some { id: file_id }
+                    } else {
+                        Block::None
+                    });
+                }
+
+                if !is_free_space {
+                    size += usize::from(digit);
+                }
+            }
+
+            if !is_free_space {
+                file_id += 1;
+            }
+
+            is_free_space = !is_free_space;
+        }
+
+        Ok(Disk { blocks, size })
+    }
+
+    fn defragment(&mut self) -> Result<usize> {
+        let mut checksum = 0;
+
+        if self.blocks.is_empty() {
+            return Ok(checksum);
+        }
+
+        let mut head_index = 0;
+        let mut tail_index = self.blocks.len() - 1;
+
+        while head_index < self.size {
+            let id = match self.blocks[head_index] {
+                Block::Some { id } => id,
+                Block::None => {
+                    while self.blocks[tail_index] == Block::None {
+                        tail_index -= 1;
+                    }
+
+                    self.blocks.swap(head_index, tail_index);
+                    match self.blocks[head_index] {
+                        Block::Some { id } => id,
+                        Block::None => bail!("invalid block"),
+                    }
+                }
+            };
+
+            checksum += head_index * id;
+            head_index += 1;
+        }
+
+        Ok(checksum)
+    }
+}
+
+pub fn checksum(file_name: &str) -> Result<(usize, usize)> {
+    let file = std::fs::File::open(file_name)?;
+
+    checksum_from_reader(std::io::BufReader::new(file))
+}
+
+pub fn checksum_from_reader(reader: impl BufRead) -> Result<(usize, usize)> {
+    let mut disk = Disk::from_reader(reader)?;
+    let checksum = disk.defragment()?;
+
+    Ok((checksum, 0))
+}
+
+pub struct Day9 {
+    disk: Disk,
+}
+
+impl Solution for Day9 {
+    const DAY: u32 = 9;
+
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self> {
+        Ok(Self {
+            disk: Disk::from_reader(input.as_bytes())?,
+        })
+    }
+
+    fn part1(&self) -> Result<usize> {
+        self.disk.clone().defragment()
+    }
+
+    fn part2(&self) -> Result<usize> {
+        bail!("part 2 is not implemented")
+    }
+}
@@ -0,0 +1,194 @@
+use std::{
+    cmp::Reverse,
+    collections::BinaryHeap,
+    fs::File,
+    io::{BufReader, BufWriter, ErrorKind, Read, Write},
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use anyhow::{ensure, Context, Result};
+
+/// Most runs merged at once, to stay well below the open file limit.
+const MAX_FAN_IN: usize = 64;
+
+const VALUE_SIZE: usize = size_of::<i128>();
+
+/// A directory for the runs of one sort, removed with everything in it when
+/// dropped.
+struct SpillDir {
+    path: PathBuf,
+    files: usize,
+}
+
+impl SpillDir {
+    fn new(parent: &Path) -> Result<Self> {
+        static COUNT: AtomicUsize = AtomicUsize::new(0);
+
+        let path = parent.join(format!(
+            "aoc-sort-{}-{}",
+            std::process::id(),
+            COUNT.fetch_add(1, Ordering::Relaxed)
+        ));
+        std::fs::create_dir_all(&path)
+            .with_context(|| format!("cannot create {}", path.display()))?;
+
+        Ok(Self { path, files: 0 })
+    }
+
+    fn next_file(&mut self) -> PathBuf {
+        self.files += 1;
+
+        self.path.join(format!("run-{}", self.files))
+    }
+}
+
+impl Drop for SpillDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.path);
+    }
+}
+
+/// Sorts more values than fit in memory: values are buffered up to
+/// `run_size` at a time, and every full buffer is sorted and spilled to a
+/// temporary file as a run. The runs are merged when read back.
+pub struct ExternalSorter {
+    dir: SpillDir,
+    run_size: usize,
+    buffer: Vec<i128>,
+    runs: Vec<PathBuf>,
+}
+
+impl ExternalSorter {
+    /// Spills runs of `run_size` values to a new directory inside `temp_dir`.
+    pub fn new(temp_dir: &Path, run_size: usize) -> Result<Self> {
+        ensure!(run_size > 0, "run size must be positive");
+
+        Ok(Self {
+            dir: SpillDir::new(temp_dir)?,
+            run_size,
+            buffer: Vec::new(),
+            runs: Vec::new(),
+        })
+    }
+
+    pub fn push(&mut self, value: i128) -> Result<()> {
+        self.buffer.push(value);
+
+        if self.buffer.len() >= self.run_size {
+            self.spill()?;
+        }
+
+        Ok(())
+    }
+
+    fn spill(&mut self) -> Result<()> {
+        self.buffer.sort_unstable();
+
+        let path = self.dir.next_file();
+        write_run(&path, self.buffer.drain(..).map(Ok))?;
+        self.runs.push(path);
+
+        Ok(())
+    }
+
+    /// Spills the last run and merges runs until few enough are left to be
+    /// read back at once.
+    pub fn finish(mut self) -> Result<SortedRuns> {
+        if !self.buffer.is_empty() {
+            self.spill()?;
+        }
+
+        while self.runs.len() > MAX_FAN_IN {
+            let mut merged = Vec::new();
+
+            for group in self.runs.chunks(MAX_FAN_IN) {
+                let path = self.dir.next_file();
+                write_run(&path, Merge::open(group)?)?;
+                merged.push(path);
+            }
+
+            for run in std::mem::replace(&mut self.runs, merged) {
+                std::fs::remove_file(run)?;
+            }
+        }
+
+        Ok(SortedRuns {
+            _dir: self.dir,
+            runs: self.runs,
+        })
+    }
+}
+
+fn write_run(path: &Path, values: impl Iterator<Item = Result<i128>>) -> Result<()> {
+    let file = File::create(path).with_context(|| format!("cannot create {}", path.display()))?;
+    let mut writer = BufWriter::new(file);
+
+    for value in values {
+        writer.write_all(&value?.to_le_bytes())?;
+    }
+    writer.flush()?;
+
+    Ok(())
+}
+
+/// The sorted runs of an [`ExternalSorter`], deleted when dropped.
+pub struct SortedRuns {
+    _dir: SpillDir,
+    runs: Vec<PathBuf>,
+}
+
+impl SortedRuns {
+    /// Reads all the values back in ascending order. Can be called again for
+    /// another pass.
+    pub fn iter(&self) -> Result<Merge> {
+        Merge::open(&self.runs)
+    }
+}
+
+/// Ascending values of several sorted runs, merged through a heap holding
+/// the next value of each run.
+pub struct Merge {
+    readers: Vec<BufReader<File>>,
+    heap: BinaryHeap<Reverse<(i128, usize)>>,
+}
+
+impl Merge {
+    fn open(runs: &[PathBuf]) -> Result<Self> {
+        let mut merge = Self {
+            readers: Vec::with_capacity(runs.len()),
+            heap: BinaryHeap::with_capacity(runs.len()),
+        };
+
+        for (index, run) in runs.iter().enumerate() {
+            let file = File::open(run).with_context(|| format!("cannot open {}", run.display()))?;
+            merge.readers.push(BufReader::new(file));
+            merge.refill(index)?;
+        }
+
+        Ok(merge)
+    }
+
+    /// Pushes the next value of run `index` on the heap, if any.
+    fn refill(&mut self, index: usize) -> Result<()> {
+        let mut bytes = [0; VALUE_SIZE];
+
+        match self.readers[index].read_exact(&mut bytes) {
+            Ok(()) => self.heap.push(Reverse((i128::from_le_bytes(bytes), index))),
+            Err(err) if err.kind() == ErrorKind::UnexpectedEof => {}
+            Err(err) => return Err(err.into()),
+        }
+
+        Ok(())
+    }
+}
+
+impl Iterator for Merge {
+    type Item = Result<i128>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let Reverse((value, index)) = self.heap.pop()?;
+
+        Some(self.refill(index).map(|_| value))
+    }
+}
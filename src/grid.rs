@@ -0,0 +1,273 @@
+use std::{
+    io::BufRead,
+    ops::{Index, IndexMut},
+};
+
+use anyhow::{bail, Result};
+
+use crate::parse::ParseError;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+    UpLeft,
+    UpRight,
+    DownLeft,
+    DownRight,
+}
+
+impl Direction {
+    pub const ORTHOGONAL: [Self; 4] = [Self::Up, Self::Right, Self::Down, Self::Left];
+
+    pub const ALL: [Self; 8] = [
+        Self::Up,
+        Self::UpRight,
+        Self::Right,
+        Self::DownRight,
+        Self::Down,
+        Self::DownLeft,
+        Self::Left,
+        Self::UpLeft,
+    ];
+
+    /// Column and row deltas of one step in this direction.
+    pub fn offset(self) -> (i64, i64) {
+        match self {
+            Self::Up => (0, -1),
+            Self::Down => (0, 1),
+            Self::Left => (-1, 0),
+            Self::Right => (1, 0),
+            Self::UpLeft => (-1, -1),
+            Self::UpRight => (1, -1),
+            Self::DownLeft => (-1, 1),
+            Self::DownRight => (1, 1),
+        }
+    }
+
+    /// The direction after a 90 degree clockwise turn.
+    pub fn turn_right(self) -> Self {
+        match self {
+            Self::Up => Self::Right,
+            Self::Right => Self::Down,
+            Self::Down => Self::Left,
+            Self::Left => Self::Up,
+            Self::UpRight => Self::DownRight,
+            Self::DownRight => Self::DownLeft,
+            Self::DownLeft => Self::UpLeft,
+            Self::UpLeft => Self::UpRight,
+        }
+    }
+
+    pub fn opposite(self) -> Self {
+        self.turn_right().turn_right()
+    }
+}
+
+/// A rectangular grid stored row by row in a single vector. Positions are
+/// `(x, y)` pairs, with `(0, 0)` at the top left corner.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            cells: vec![value; width * height],
+            width,
+            height,
+        }
+    }
+
+    /// Parses one row per non-empty line and one cell per character of the
+    /// input of `day`. `parse_cell` gets the position of the cell and returns
+    /// the reason why the character is invalid, if so. All rows must have the
+    /// same width.
+    pub fn parse(
+        input: &str,
+        day: u32,
+        parse_cell: impl FnMut(usize, usize, char) -> Result<T, String>,
+    ) -> Result<Self> {
+        Self::from_reader(input.as_bytes(), day, parse_cell)
+    }
+
+    pub fn from_reader(
+        reader: impl BufRead,
+        day: u32,
+        mut parse_cell: impl FnMut(usize, usize, char) -> Result<T, String>,
+    ) -> Result<Self> {
+        let mut cells = Vec::new();
+        let mut width = 0;
+        let mut height = 0;
+
+        for (index, line) in reader.lines().enumerate() {
+            let line = line?;
+
+            if line.is_empty() {
+                continue;
+            }
+
+            let row_start = cells.len();
+            for (x, c) in line.chars().enumerate() {
+                let cell = parse_cell(x, height, c)
+                    .map_err(|reason| ParseError::new(day, index + 1, x + 1, c, reason))?;
+                cells.push(cell);
+            }
+
+            let row_width = cells.len() - row_start;
+            if height == 0 {
+                width = row_width;
+            } else if row_width != width {
+                bail!(ParseError::new(
+                    day,
+                    index + 1,
+                    row_width.min(width) + 1,
+                    line,
+                    format!("row has width {row_width}, expected {width}"),
+                ));
+            }
+
+            height += 1;
+        }
+
+        Ok(Self {
+            cells,
+            width,
+            height,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        if x < self.width && y < self.height {
+            self.cells.get(y * self.width + x)
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        if x < self.width && y < self.height {
+            self.cells.get_mut(y * self.width + x)
+        } else {
+            None
+        }
+    }
+
+    /// Converts signed coordinates into a position, if it is inside the grid.
+    pub fn position(&self, x: i64, y: i64) -> Option<(usize, usize)> {
+        let x = usize::try_from(x).ok()?;
+        let y = usize::try_from(y).ok()?;
+
+        (x < self.width && y < self.height).then_some((x, y))
+    }
+
+    /// The position one step away in `direction`, if it is inside the grid.
+    pub fn step(&self, x: usize, y: usize, direction: Direction) -> Option<(usize, usize)> {
+        let (dx, dy) = direction.offset();
+
+        self.position(x as i64 + dx, y as i64 + dy)
+    }
+
+    /// The positions around `(x, y)` in all eight directions that are inside
+    /// the grid.
+    pub fn neighbours(
+        &self,
+        x: usize,
+        y: usize,
+    ) -> impl Iterator<Item = (Direction, (usize, usize))> + '_ {
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |direction| Some((direction, self.step(x, y, direction)?)))
+    }
+
+    /// The positions from `(x, y)`, included, to the edge of the grid in
+    /// `direction`.
+    pub fn walk(
+        &self,
+        x: usize,
+        y: usize,
+        direction: Direction,
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        std::iter::successors(self.get(x, y).map(|_| (x, y)), move |&(x, y)| {
+            self.step(x, y, direction)
+        })
+    }
+
+    /// The cells from `(x, y)`, included, to the edge of the grid in
+    /// `direction`.
+    pub fn line(&self, x: usize, y: usize, direction: Direction) -> impl Iterator<Item = &T> + '_ {
+        self.walk(x, y, direction).map(|(x, y)| &self[(x, y)])
+    }
+
+    /// Every full line of cells running in `direction`: the rows for
+    /// [`Direction::Right`], the columns for [`Direction::Down`], the
+    /// diagonals for [`Direction::DownRight`] and so on.
+    pub fn lines(
+        &self,
+        direction: Direction,
+    ) -> impl Iterator<Item = impl Iterator<Item = &T> + '_> + '_ {
+        self.positions()
+            .filter(move |&(x, y)| self.step(x, y, direction.opposite()).is_none())
+            .map(move |(x, y)| self.line(x, y, direction))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> + '_ {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> + '_ {
+        self.line(x, 0, Direction::Down)
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        (0..self.height).flat_map(|y| (0..self.width).map(move |x| (x, y)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> + '_ {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        self.get(x, y)
+            .unwrap_or_else(|| panic!("position ({x}, {y}) is outside the grid"))
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        self.get_mut(x, y)
+            .unwrap_or_else(|| panic!("position ({x}, {y}) is outside the grid"))
+    }
+}
@@ -0,0 +1,47 @@
+use std::{fmt::Display, io::BufRead};
+
+use anyhow::Result;
+
+pub mod answers;
+pub mod cli;
+pub mod day1;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+pub mod external_sort;
+pub mod grid;
+pub mod parse;
+pub mod runner;
+
+/// A puzzle solver for one day of the calendar.
+///
+/// The input is parsed once by [`Solution::parse`] and both parts are then
+/// solved from the parsed representation.
+///
+/// The `*_from_reader` functions of each day accept any [`BufRead`], so
+/// in-memory text can be passed as `input.as_bytes()`.
+pub trait Solution: Sized {
+    /// Day of the calendar solved by this type.
+    const DAY: u32;
+
+    type Answer1: Display;
+    type Answer2: Display;
+
+    fn parse(input: &str) -> Result<Self>;
+
+    fn from_reader(mut reader: impl BufRead) -> Result<Self> {
+        let mut input = String::new();
+        reader.read_to_string(&mut input)?;
+
+        Self::parse(&input)
+    }
+
+    fn part1(&self) -> Result<Self::Answer1>;
+
+    fn part2(&self) -> Result<Self::Answer2>;
+}
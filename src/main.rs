@@ -0,0 +1,232 @@
+use std::{path::PathBuf, time::Duration};
+
+use anyhow::{bail, Context, Result};
+use clap::{Parser, Subcommand};
+
+use aoc::{
+    answers::{Answers, DEFAULT_ANSWERS},
+    cli::{format_answer, read_input, write_json, Format},
+    runner::{bench_day, default_input, run_day, DayResult, Part, Timings, DAYS},
+};
+
+#[derive(Parser)]
+struct Args {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(clap::Args)]
+struct Selection {
+    /// Day number, or `all`
+    #[arg(value_parser = parse_days)]
+    day: Days,
+
+    /// Solve only this part
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+
+    /// Puzzle input, or `-` for stdin; defaults to `<day>.input` at the
+    /// repository root
+    #[arg(long)]
+    input: Option<PathBuf>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solve one day, or `all` of them
+    Run {
+        #[command(flatten)]
+        selection: Selection,
+
+        /// Output format
+        #[arg(long, value_enum, default_value_t)]
+        format: Format,
+    },
+    /// Compare the computed answers against the answers manifest
+    Verify {
+        #[command(flatten)]
+        selection: Selection,
+
+        /// Answers manifest
+        #[arg(long, default_value = DEFAULT_ANSWERS)]
+        answers: String,
+    },
+    /// Time parsing and each part over several iterations
+    Bench {
+        #[command(flatten)]
+        selection: Selection,
+
+        /// Number of iterations
+        #[arg(short = 'n', long, default_value_t = 10)]
+        iterations: usize,
+    },
+}
+
+#[derive(Clone)]
+enum Days {
+    All,
+    Day(u32),
+}
+
+fn parse_days(s: &str) -> Result<Days> {
+    if s == "all" {
+        return Ok(Days::All);
+    }
+
+    let day = s.parse::<u32>().context("expected a day number or `all`")?;
+    if !DAYS.contains(&day) {
+        bail!("day {day} is not solved");
+    }
+
+    Ok(Days::Day(day))
+}
+
+impl Selection {
+    fn part(&self) -> Result<Option<Part>> {
+        self.part.map(Part::from_number).transpose()
+    }
+
+    /// Reads the input of every selected day and calls `solve` with it.
+    fn solve<T>(&self, mut solve: impl FnMut(u32, &str) -> Result<T>) -> Result<Vec<T>> {
+        let days = match self.day {
+            Days::Day(day) => vec![day],
+            Days::All => {
+                if self.input.is_some() {
+                    bail!("--input cannot be used with all days");
+                }
+
+                DAYS.to_vec()
+            }
+        };
+
+        days.into_iter()
+            .map(|day| {
+                let input = self.input.clone().unwrap_or_else(|| default_input(day));
+                solve(day, &read_input(Some(&input))?)
+            })
+            .collect()
+    }
+}
+
+fn print_summary(results: &[DayResult]) {
+    let answer_width = results
+        .iter()
+        .flat_map(|day| &day.parts)
+        .map(|result| format_answer(result).len())
+        .max()
+        .unwrap_or(0)
+        .max("Answer".len());
+
+    println!("Day  Stage   {:answer_width$}  Time", "Answer");
+
+    let mut total = Duration::ZERO;
+    for day in results {
+        println!(
+            "{:>3}  parse   {:answer_width$}  {:?}",
+            day.day, "", day.parse_elapsed
+        );
+        total += day.parse_elapsed;
+
+        for result in &day.parts {
+            println!(
+                "{:>3}  part {}  {:answer_width$}  {:?}",
+                result.day,
+                result.part,
+                format_answer(result),
+                result.elapsed
+            );
+            total += result.elapsed;
+        }
+    }
+
+    println!("Total: {total:?}");
+}
+
+fn print_bench(timings: &[Timings]) {
+    println!(
+        "Day  Stage   {:>12}  {:>12}  {:>12}",
+        "Min", "Median", "Max"
+    );
+
+    for timings in timings {
+        let stage = match timings.part {
+            Some(part) => format!("part {part}"),
+            None => "parse".to_owned(),
+        };
+
+        match &timings.error {
+            Some(err) => println!("{:>3}  {stage:6}  error: {err}", timings.day),
+            None => println!(
+                "{:>3}  {stage:6}  {:>12}  {:>12}  {:>12}",
+                timings.day,
+                format!("{:?}", timings.min()),
+                format!("{:?}", timings.median()),
+                format!("{:?}", timings.max())
+            ),
+        }
+    }
+}
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+
+    match args.command {
+        Command::Run { selection, format } => {
+            let part = selection.part()?;
+            let results = selection.solve(|day, input| run_day(day, input, part))?;
+
+            match format {
+                Format::Text if matches!(selection.day, Days::All) => print_summary(&results),
+                Format::Text => {
+                    for day in results {
+                        println!("Day {} parse: {:?}", day.day, day.parse_elapsed);
+
+                        for result in day.parts {
+                            println!(
+                                "Day {} part {}: {} ({:?})",
+                                result.day,
+                                result.part,
+                                format_answer(&result),
+                                result.elapsed
+                            );
+                        }
+                    }
+                }
+                Format::Json => write_json(std::io::stdout().lock(), &results)?,
+            }
+        }
+        Command::Verify { selection, answers } => {
+            let answers = Answers::from_file(&answers)?;
+            let part = selection.part()?;
+            let results = selection.solve(|day, input| run_day(day, input, part))?;
+            let parts = results.iter().flat_map(|day| &day.parts);
+            let mismatches = answers.verify(parts.clone());
+
+            for mismatch in &mismatches {
+                println!("Day {} part {}:", mismatch.day, mismatch.part);
+                println!("- {}", mismatch.expected);
+                println!("+ {}", mismatch.actual);
+            }
+
+            if !mismatches.is_empty() {
+                bail!("{} answers do not match", mismatches.len());
+            }
+
+            let verified = parts
+                .filter(|result| answers.get(result.day, result.part).is_some())
+                .count();
+            println!("{verified} answers match");
+        }
+        Command::Bench {
+            selection,
+            iterations,
+        } => {
+            let part = selection.part()?;
+            let timings = selection.solve(|day, input| bench_day(day, input, part, iterations))?;
+
+            print_bench(&timings.into_iter().flatten().collect::<Vec<_>>());
+        }
+    }
+
+    Ok(())
+}
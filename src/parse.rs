@@ -0,0 +1,78 @@
+use std::{fmt::Display, str::FromStr};
+
+/// A malformed puzzle input. Lines and columns start at 1 and columns count
+/// characters, not bytes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub day: u32,
+    pub line: usize,
+    pub column: usize,
+    /// The offending fragment of the input.
+    pub text: String,
+    pub reason: String,
+}
+
+impl ParseError {
+    pub fn new(
+        day: u32,
+        line: usize,
+        column: usize,
+        text: impl Into<String>,
+        reason: impl Into<String>,
+    ) -> Self {
+        Self {
+            day,
+            line,
+            column,
+            text: text.into(),
+            reason: reason.into(),
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "day {} input, line {}, column {}: {}: {:?}",
+            self.day, self.line, self.column, self.reason, self.text
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Splits `line` at whitespace, skipping empty fields, and yields each field
+/// with its column.
+pub(crate) fn fields(line: &str) -> impl Iterator<Item = (usize, &str)> {
+    split(line, char::is_whitespace).filter(|(_, field)| !field.is_empty())
+}
+
+/// Splits `line` at every character matching `is_separator` and yields each
+/// field with its column, including empty ones.
+pub(crate) fn split(
+    line: &str,
+    is_separator: impl Fn(char) -> bool,
+) -> impl Iterator<Item = (usize, &str)> {
+    let mut column = 1;
+
+    line.split(is_separator).map(move |field| {
+        let start = column;
+        column += field.chars().count() + 1;
+
+        (start, field)
+    })
+}
+
+/// Parses a whitespace-trimmed number found at `line` and `column`.
+pub(crate) fn number<T: FromStr>(
+    day: u32,
+    line: usize,
+    column: usize,
+    field: &str,
+) -> Result<T, ParseError> {
+    field
+        .trim()
+        .parse()
+        .map_err(|_| ParseError::new(day, line, column, field, "invalid number"))
+}
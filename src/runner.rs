@@ -0,0 +1,236 @@
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use anyhow::{bail, Result};
+
+use crate::{
+    day1::Day1, day2::Day2, day3::Day3, day4::Day4, day5::Day5, day6::Day6, day7::Day7, day8::Day8,
+    day9::Day9, Solution,
+};
+
+/// Days with a solver, in calendar order.
+pub const DAYS: &[u32] = &[1, 2, 3, 4, 5, 6, 7, 8, 9];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub fn from_number(number: u8) -> Result<Self> {
+        match number {
+            1 => Ok(Self::One),
+            2 => Ok(Self::Two),
+            _ => bail!("invalid part: {number}"),
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let part = match self {
+            Self::One => "1",
+            Self::Two => "2",
+        };
+
+        f.pad(part)
+    }
+}
+
+pub struct PartResult {
+    pub day: u32,
+    pub part: Part,
+    pub answer: Result<String>,
+    pub elapsed: Duration,
+}
+
+pub struct DayResult {
+    pub day: u32,
+    pub parse_elapsed: Duration,
+    pub parts: Vec<PartResult>,
+}
+
+/// Timing samples of one stage of a solver, collected by [`bench`].
+pub struct Timings {
+    pub day: u32,
+    /// The solved part, or `None` for parsing.
+    pub part: Option<Part>,
+    /// The first error returned by the stage, after which it is no longer
+    /// sampled.
+    pub error: Option<anyhow::Error>,
+    samples: Vec<Duration>,
+}
+
+impl Timings {
+    fn new(day: u32, part: Option<Part>) -> Self {
+        Self {
+            day,
+            part,
+            error: None,
+            samples: Vec::new(),
+        }
+    }
+
+    fn sample<T>(&mut self, stage: impl FnOnce() -> Result<T>) {
+        if self.error.is_some() {
+            return;
+        }
+
+        let start = Instant::now();
+        // Keep the optimizer from discarding the unused output
+        match std::hint::black_box(stage()) {
+            Ok(_) => self.samples.push(start.elapsed()),
+            Err(err) => self.error = Some(err),
+        }
+    }
+
+    pub fn min(&self) -> Duration {
+        self.samples.iter().min().copied().unwrap_or_default()
+    }
+
+    pub fn median(&self) -> Duration {
+        let mut samples = self.samples.clone();
+        samples.sort();
+
+        samples.get(samples.len() / 2).copied().unwrap_or_default()
+    }
+
+    pub fn max(&self) -> Duration {
+        self.samples.iter().max().copied().unwrap_or_default()
+    }
+}
+
+/// Default puzzle input of a day, relative to the repository root.
+pub fn default_input(day: u32) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join(format!("{day}.input"))
+}
+
+fn timed<T: Display>(day: u32, part: Part, solve: impl FnOnce() -> Result<T>) -> PartResult {
+    let start = Instant::now();
+    let answer = solve().map(|answer| answer.to_string());
+    let elapsed = start.elapsed();
+
+    PartResult {
+        day,
+        part,
+        answer,
+        elapsed,
+    }
+}
+
+/// Parses the input and solves the requested part, or both parts if `part`
+/// is `None`.
+pub fn run<S: Solution>(input: &str, part: Option<Part>) -> Result<DayResult> {
+    let start = Instant::now();
+    let solution = S::parse(input)?;
+    let parse_elapsed = start.elapsed();
+
+    Ok(solve(&solution, parse_elapsed, part))
+}
+
+/// Solves the requested part of an already parsed `solution`, or both parts
+/// if `part` is `None`.
+pub fn solve<S: Solution>(solution: &S, parse_elapsed: Duration, part: Option<Part>) -> DayResult {
+    let mut parts = Vec::new();
+
+    if part != Some(Part::Two) {
+        parts.push(timed(S::DAY, Part::One, || solution.part1()));
+    }
+
+    if part != Some(Part::One) {
+        parts.push(timed(S::DAY, Part::Two, || solution.part2()));
+    }
+
+    DayResult {
+        day: S::DAY,
+        parse_elapsed,
+        parts,
+    }
+}
+
+/// Both parts of a day answered together in a single pass over the input.
+/// Reading and solving cannot be told apart, so the whole pass is reported
+/// as parsing time.
+pub fn single_pass(day: u32, elapsed: Duration, answers: [Result<String>; 2]) -> DayResult {
+    let parts = [Part::One, Part::Two]
+        .into_iter()
+        .zip(answers)
+        .map(|(part, answer)| PartResult {
+            day,
+            part,
+            answer,
+            elapsed: Duration::ZERO,
+        })
+        .collect();
+
+    DayResult {
+        day,
+        parse_elapsed: elapsed,
+        parts,
+    }
+}
+
+/// Parses the input and solves the requested parts `iterations` times,
+/// timing each stage separately.
+pub fn bench<S: Solution>(
+    input: &str,
+    part: Option<Part>,
+    iterations: usize,
+) -> Result<Vec<Timings>> {
+    let mut parse = Timings::new(S::DAY, None);
+    let mut part1 = Timings::new(S::DAY, Some(Part::One));
+    let mut part2 = Timings::new(S::DAY, Some(Part::Two));
+
+    for _ in 0..iterations {
+        let start = Instant::now();
+        let solution = std::hint::black_box(S::parse(input)?);
+        parse.samples.push(start.elapsed());
+
+        if part != Some(Part::Two) {
+            part1.sample(|| solution.part1());
+        }
+
+        if part != Some(Part::One) {
+            part2.sample(|| solution.part2());
+        }
+    }
+
+    Ok([parse, part1, part2]
+        .into_iter()
+        .filter(|timings| !timings.samples.is_empty() || timings.error.is_some())
+        .collect())
+}
+
+macro_rules! dispatch {
+    ($day:expr, $solve:ident($($arg:expr),*)) => {
+        match $day {
+            1 => $solve::<Day1>($($arg),*),
+            2 => $solve::<Day2>($($arg),*),
+            3 => $solve::<Day3>($($arg),*),
+            4 => $solve::<Day4>($($arg),*),
+            5 => $solve::<Day5>($($arg),*),
+            6 => $solve::<Day6>($($arg),*),
+            7 => $solve::<Day7>($($arg),*),
+            8 => $solve::<Day8>($($arg),*),
+            9 => $solve::<Day9>($($arg),*),
+            day => bail!("day {day} is not solved"),
+        }
+    };
+}
+
+pub fn run_day(day: u32, input: &str, part: Option<Part>) -> Result<DayResult> {
+    dispatch!(day, run(input, part))
+}
+
+pub fn bench_day(
+    day: u32,
+    input: &str,
+    part: Option<Part>,
+    iterations: usize,
+) -> Result<Vec<Timings>> {
+    dispatch!(day, bench(input, part, iterations))
+}
@@ -0,0 +1,50 @@
+use std::time::Duration;
+
+use aoc::{
+    answers::Answers,
+    runner::{Part, PartResult},
+};
+
+fn result(day: u32, part: Part, answer: &str) -> PartResult {
+    PartResult {
+        day,
+        part,
+        answer: Ok(answer.to_owned()),
+        elapsed: Duration::ZERO,
+    }
+}
+
+#[test]
+fn parse() {
+    let answers = Answers::parse("# day part answer\n1 1 11\n\n1 2 31\n").unwrap();
+
+    assert_eq!(answers.get(1, Part::One), Some("11"));
+    assert_eq!(answers.get(1, Part::Two), Some("31"));
+    assert_eq!(answers.get(2, Part::One), None);
+}
+
+#[test]
+fn parse_invalid() {
+    assert!(Answers::parse("1 3 11\n").is_err());
+    assert!(Answers::parse("1 1\n").is_err());
+    assert!(Answers::parse("1 1 11 12\n").is_err());
+    assert!(Answers::parse("1 1 11\n1 1 12\n").is_err());
+}
+
+#[test]
+fn verify() {
+    let answers = Answers::parse("1 1 11\n1 2 31\n").unwrap();
+
+    let results = [
+        result(1, Part::One, "11"),
+        result(1, Part::Two, "32"),
+        result(2, Part::One, "2"),
+    ];
+    let mismatches = answers.verify(&results);
+
+    assert_eq!(mismatches.len(), 1);
+    assert_eq!(mismatches[0].day, 1);
+    assert_eq!(mismatches[0].part, Part::Two);
+    assert_eq!(mismatches[0].expected, "31");
+    assert_eq!(mismatches[0].actual, "32");
+}
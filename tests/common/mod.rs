@@ -0,0 +1,61 @@
+//! Random reports and safety policies shared by the day 2 tests.
+
+use aoc::day2::SafetyPolicy;
+
+/// splitmix64, enough to generate reports without a dependency.
+pub struct Rng(pub u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    pub fn below(&mut self, bound: u64) -> u64 {
+        self.next() % bound
+    }
+}
+
+/// A random walk with mostly small steps, so that many reports are safe or
+/// one level away from it.
+pub fn report(rng: &mut Rng) -> Vec<u32> {
+    let len = rng.below(10) as usize;
+    let mut level = rng.below(20) as i64 + 10;
+
+    (0..len)
+        .map(|_| {
+            let step = match rng.below(10) {
+                0 => rng.below(15) as i64 - 7,
+                1 => 0,
+                _ => rng.below(4) as i64 + 1,
+            };
+            let step = if rng.below(8) == 0 { -step } else { step };
+            level = (level + step).max(0);
+
+            level as u32
+        })
+        .collect()
+}
+
+/// The default policy and a variant changing each of its rules.
+pub fn policies() -> [SafetyPolicy; 4] {
+    [
+        SafetyPolicy::default(),
+        SafetyPolicy {
+            allow_plateaus: true,
+            ..SafetyPolicy::default()
+        },
+        SafetyPolicy {
+            consistent_direction: false,
+            ..SafetyPolicy::default()
+        },
+        SafetyPolicy {
+            min_step: 2,
+            max_step: 5,
+            ..SafetyPolicy::default()
+        },
+    ]
+}
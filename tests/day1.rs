@@ -0,0 +1,142 @@
+use aoc::{
+    day1::{external_distance_and_similarity, Contribution, LocationLists, Pair},
+    parse::ParseError,
+};
+
+#[test]
+fn similarity_with_runs() {
+    let input = "5 5\n5 1\n2 5\n5 5\n7 2\n1 9\n";
+    let lists = LocationLists::from_reader(input.as_bytes()).unwrap();
+
+    // 5 appears 3 times on the left and 3 times on the right, 2 and 1 once
+    assert_eq!(lists.similarity().unwrap(), 5 * 3 * 3 + 2 + 1);
+}
+
+#[test]
+fn matrices() {
+    let input = "3 4 3\n4 3 1\n2 5 3\n";
+    let lists = LocationLists::from_reader(input.as_bytes()).unwrap();
+
+    assert_eq!(lists.columns(), 3);
+    assert_eq!(
+        lists.distance_matrix().unwrap(),
+        vec![vec![0, 3, 2], vec![3, 0, 5], vec![2, 5, 0]]
+    );
+    // 3 appears twice in column 3, so it counts four times on the diagonal
+    assert_eq!(
+        lists.similarity_matrix().unwrap(),
+        vec![vec![9, 7, 6], vec![7, 12, 6], vec![6, 6, 13]]
+    );
+    assert_eq!(
+        lists.distance().unwrap(),
+        lists.distance_between(0, 1).unwrap()
+    );
+}
+
+#[test]
+fn signed_and_large_ids() {
+    let input = "-3 4\n18446744073709551616 -3\n-3 18446744073709551616\n";
+    let lists = LocationLists::from_reader(input.as_bytes()).unwrap();
+
+    assert_eq!(lists.distance().unwrap(), 7);
+    assert_eq!(
+        lists.similarity().unwrap(),
+        2 * -3 + 18_446_744_073_709_551_616
+    );
+}
+
+#[test]
+fn overflow() {
+    let max = i128::MAX;
+    let min = i128::MIN;
+
+    let input = format!("{min} {max}\n");
+    let lists = LocationLists::from_reader(input.as_bytes()).unwrap();
+    assert_eq!(lists.distance().unwrap(), u128::MAX);
+
+    let input = format!("{min} {max}\n{min} {max}\n");
+    let lists = LocationLists::from_reader(input.as_bytes()).unwrap();
+    let err = lists.distance().unwrap_err();
+    assert_eq!(err.to_string(), "distance of columns 1 and 2 overflows");
+
+    let input = format!("{max} {max}\n{max} {max}\n");
+    let lists = LocationLists::from_reader(input.as_bytes()).unwrap();
+    assert!(lists.similarity().is_err());
+
+    let input = format!("1 {max}0\n");
+    let err = LocationLists::from_reader(input.as_bytes()).err().unwrap();
+    let err = err.downcast::<ParseError>().unwrap();
+    assert_eq!((err.line, err.column), (1, 3));
+    assert_eq!(err.reason, "number is too large");
+}
+
+#[test]
+fn pairs_and_contributions() {
+    let input = "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n";
+    let lists = LocationLists::from_reader(input.as_bytes()).unwrap();
+
+    let pairs = lists.pairs().collect::<Vec<_>>();
+    assert_eq!(pairs.len(), 6);
+    assert_eq!(
+        pairs[..2],
+        [
+            Pair {
+                left: 1,
+                right: 3,
+                distance: 2
+            },
+            Pair {
+                left: 2,
+                right: 3,
+                distance: 1
+            }
+        ]
+    );
+    assert_eq!(
+        pairs.iter().map(|pair| pair.distance).sum::<u128>(),
+        lists.distance().unwrap()
+    );
+
+    let contributions = lists
+        .contributions()
+        .collect::<anyhow::Result<Vec<_>>>()
+        .unwrap();
+    assert_eq!(
+        contributions,
+        [
+            Contribution {
+                id: 3,
+                left_count: 3,
+                right_count: 3,
+                score: 27
+            },
+            Contribution {
+                id: 4,
+                left_count: 1,
+                right_count: 1,
+                score: 4
+            }
+        ]
+    );
+    assert_eq!(lists.similarity().unwrap(), 31);
+}
+
+#[test]
+fn external_matches_in_memory() {
+    let input = (0..500)
+        .map(|i: i64| format!("{}   {}\n", (i * 37) % 101 - 50, (i * 53) % 89))
+        .collect::<String>();
+    let lists = LocationLists::from_reader(input.as_bytes()).unwrap();
+
+    for run_size in [1, 7, 1000] {
+        assert_eq!(
+            external_distance_and_similarity(input.as_bytes(), &std::env::temp_dir(), run_size)
+                .unwrap(),
+            (lists.distance().unwrap(), lists.similarity().unwrap())
+        );
+    }
+
+    let err = external_distance_and_similarity("1 2\n3\n".as_bytes(), &std::env::temp_dir(), 4)
+        .unwrap_err();
+    assert_eq!(err.downcast::<ParseError>().unwrap().line, 2);
+}
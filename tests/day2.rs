@@ -0,0 +1,231 @@
+mod common;
+
+use aoc::{
+    day2::{
+        min_removals, monitor_feed, removed_levels, report_is_safe, report_is_safe_recursive,
+        safe_counts_from_reader, verdict, ReportChecker, ReportState, SafeCounts, SafetyPolicy,
+        Verdict, Violation,
+    },
+    parse::ParseError,
+};
+
+use common::{policies, report, Rng};
+
+#[test]
+fn default_policy() {
+    let policy = SafetyPolicy::default();
+
+    assert!(report_is_safe(&policy, &[7, 6, 4, 2, 1]));
+    assert!(!report_is_safe(&policy, &[1, 2, 7, 8, 9]));
+    assert!(!report_is_safe(&policy, &[8, 6, 4, 4, 1]));
+    assert_eq!(policy.check_step(4, 4, None), Err(Violation::Plateau));
+    assert_eq!(policy.check_step(2, 7, None), Err(Violation::StepTooLarge));
+    assert_eq!(
+        policy.check_step(3, 2, Some(true)),
+        Err(Violation::DirectionChange)
+    );
+}
+
+#[test]
+fn custom_policies() {
+    let plateaus = SafetyPolicy {
+        allow_plateaus: true,
+        ..SafetyPolicy::default()
+    };
+    assert!(report_is_safe(&plateaus, &[8, 6, 4, 4, 1]));
+    // A plateau keeps the direction set by the previous steps
+    assert!(!report_is_safe(&plateaus, &[1, 2, 2, 1]));
+
+    let any_direction = SafetyPolicy {
+        consistent_direction: false,
+        ..SafetyPolicy::default()
+    };
+    assert!(report_is_safe(&any_direction, &[1, 3, 2, 4, 5]));
+    assert!(!report_is_safe(&any_direction, &[1, 3, 3, 4]));
+
+    let wide = SafetyPolicy {
+        min_step: 2,
+        max_step: 5,
+        ..SafetyPolicy::default()
+    };
+    assert!(report_is_safe(&wide, &[1, 6, 8, 12]));
+    assert_eq!(wide.check_step(1, 2, None), Err(Violation::StepTooSmall));
+    assert!(!report_is_safe(&wide, &[1, 2, 7, 9]));
+    assert!(report_is_safe_recursive(
+        &wide,
+        None,
+        None,
+        false,
+        &[1, 2, 7, 9]
+    ));
+}
+
+/// Fewest removals found by trying every subset of kept levels.
+fn brute_force_min_removals(policy: &SafetyPolicy, levels: &[u32]) -> usize {
+    (0u32..1 << levels.len())
+        .filter_map(|kept| {
+            let report = levels
+                .iter()
+                .enumerate()
+                .filter(|&(index, _)| kept & (1 << index) != 0)
+                .map(|(_, &level)| level)
+                .collect::<Vec<_>>();
+
+            report_is_safe(policy, &report).then(|| levels.len() - report.len())
+        })
+        .min()
+        .unwrap()
+}
+
+#[test]
+fn min_removals_matches_brute_force() {
+    let mut rng = Rng(0x9e37_79b9);
+    for _ in 0..2000 {
+        let levels = report(&mut rng);
+
+        for policy in &policies() {
+            let expected = brute_force_min_removals(policy, &levels);
+
+            let removed = removed_levels(policy, &levels, levels.len()).unwrap();
+            let kept = (0..levels.len())
+                .filter(|index| !removed.contains(index))
+                .map(|index| levels[index])
+                .collect::<Vec<_>>();
+            assert_eq!(removed.len(), expected);
+            assert!(
+                report_is_safe(policy, &kept),
+                "{levels:?} without {removed:?}"
+            );
+
+            for max_removed in 0..=levels.len() {
+                assert_eq!(
+                    min_removals(policy, &levels, max_removed),
+                    (expected <= max_removed).then_some(expected),
+                    "{levels:?} with {policy:?}, removing up to {max_removed}"
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn verdicts() {
+    let policy = SafetyPolicy::default();
+
+    assert_eq!(verdict(&policy, &[7, 6, 4, 2, 1], 1), Verdict::Safe);
+    assert_eq!(
+        verdict(&policy, &[1, 2, 7, 8, 9], 1),
+        Verdict::Unsafe {
+            index: 1,
+            violation: Violation::StepTooLarge
+        }
+    );
+    assert_eq!(
+        verdict(&policy, &[8, 6, 4, 4, 1], 1),
+        Verdict::Dampened { removed: vec![3] }
+    );
+    assert_eq!(
+        verdict(&policy, &[1, 2, 7, 8, 9], 2),
+        Verdict::Dampened {
+            removed: vec![0, 1]
+        }
+    );
+    assert_eq!(
+        verdict(&policy, &[1, 3, 2, 4, 3], 1),
+        Verdict::Unsafe {
+            index: 1,
+            violation: Violation::DirectionChange
+        }
+    );
+}
+
+#[test]
+fn parallel_counts() {
+    let example = "7 6 4 2 1\n1 2 7 8 9\n9 7 6 2 1\n1 3 2 4 5\n8 6 4 4 1\n1 3 6 7 9\n";
+    // Several chunks, the last one partial
+    let input = example.repeat(2000);
+    let policy = SafetyPolicy::default();
+
+    for threads in [1, 2, 5] {
+        let counts = safe_counts_from_reader(input.as_bytes(), &policy, 1, threads).unwrap();
+        assert_eq!(
+            counts,
+            SafeCounts {
+                safe: 2 * 2000,
+                dampened: 4 * 2000
+            }
+        );
+    }
+
+    let mut lines = input.lines().map(str::to_owned).collect::<Vec<_>>();
+    lines[9000] = "1 x 3".to_owned();
+    lines[11000] = "1 y 3".to_owned();
+    let input = lines.join("\n");
+    for threads in [1, 4] {
+        let err = safe_counts_from_reader(input.as_bytes(), &policy, 1, threads).unwrap_err();
+        assert_eq!(err.downcast::<ParseError>().unwrap().line, 9001);
+    }
+}
+
+#[test]
+fn report_checker() {
+    let mut checker = ReportChecker::new(SafetyPolicy::default(), 1);
+
+    let states = [1, 2, 7, 8, 9].map(|level| checker.push(level));
+    assert_eq!(
+        states,
+        [
+            ReportState::Safe,
+            ReportState::Safe,
+            ReportState::Dampened { removed: 1 },
+            ReportState::Unrecoverable,
+            ReportState::Unrecoverable
+        ]
+    );
+
+    checker.reset();
+    let states = [8, 6, 4, 4, 1].map(|level| checker.push(level));
+    assert_eq!(
+        states[2..],
+        [
+            ReportState::Safe,
+            ReportState::Dampened { removed: 1 },
+            ReportState::Dampened { removed: 1 }
+        ]
+    );
+    assert_eq!(checker.len(), 5);
+}
+
+#[test]
+fn feed() {
+    let mut events = Vec::new();
+    monitor_feed(
+        "1 2 7 8\n 9 7\n".as_bytes(),
+        &SafetyPolicy::default(),
+        1,
+        |event| {
+            events.push((event.report, event.index, event.level, event.state));
+            Ok(())
+        },
+    )
+    .unwrap();
+
+    assert_eq!(
+        events,
+        [
+            (1, 0, 1, ReportState::Safe),
+            (1, 1, 2, ReportState::Safe),
+            (1, 2, 7, ReportState::Dampened { removed: 1 }),
+            (1, 3, 8, ReportState::Unrecoverable),
+            (2, 0, 9, ReportState::Safe),
+            (2, 1, 7, ReportState::Safe)
+        ]
+    );
+
+    let err = monitor_feed("1 2\n3 -4".as_bytes(), &SafetyPolicy::default(), 1, |_| {
+        Ok(())
+    })
+    .unwrap_err();
+    let err = err.downcast::<ParseError>().unwrap();
+    assert_eq!((err.line, err.column, err.text.as_str()), (2, 3, "-4"));
+}
@@ -0,0 +1,84 @@
+//! Randomized cross-check of the recursive dampener against removing each
+//! level in turn.
+
+mod common;
+
+use aoc::day2::{report_is_safe, report_is_safe_recursive, SafetyPolicy};
+
+use common::{policies, report, Rng};
+
+const SEED: u64 = 0x5eed_da72;
+const CASES: usize = 20_000;
+
+fn oracle(policy: &SafetyPolicy, levels: &[u32]) -> bool {
+    report_is_safe(policy, levels)
+        || (0..levels.len()).any(|index| {
+            let mut levels = levels.to_vec();
+            levels.remove(index);
+
+            report_is_safe(policy, &levels)
+        })
+}
+
+fn recursive(policy: &SafetyPolicy, levels: &[u32]) -> bool {
+    report_is_safe_recursive(policy, None, None, false, levels)
+}
+
+fn fails(policy: &SafetyPolicy, levels: &[u32]) -> bool {
+    recursive(policy, levels) != oracle(policy, levels)
+}
+
+/// Smaller variants of a report: one level fewer, or one level closer to 0.
+fn candidates(levels: &[u32]) -> Vec<Vec<u32>> {
+    let mut candidates = Vec::new();
+
+    for index in 0..levels.len() {
+        let mut shorter = levels.to_vec();
+        shorter.remove(index);
+        candidates.push(shorter);
+    }
+
+    for index in 0..levels.len() {
+        for smaller in [0, levels[index] / 2, levels[index].saturating_sub(1)] {
+            if smaller < levels[index] {
+                let mut lower = levels.to_vec();
+                lower[index] = smaller;
+                candidates.push(lower);
+            }
+        }
+    }
+
+    candidates
+}
+
+/// Shrinks a failing report until none of its smaller variants fails.
+fn shrink(policy: &SafetyPolicy, mut levels: Vec<u32>) -> Vec<u32> {
+    while let Some(smaller) = candidates(&levels)
+        .into_iter()
+        .find(|candidate| fails(policy, candidate))
+    {
+        levels = smaller;
+    }
+
+    levels
+}
+
+#[test]
+fn recursive_dampener_matches_oracle() {
+    let mut rng = Rng(SEED);
+    for case in 0..CASES {
+        let levels = report(&mut rng);
+
+        for policy in &policies() {
+            if fails(policy, &levels) {
+                let shrunk = shrink(policy, levels.clone());
+                panic!(
+                    "case {case} of seed {SEED:#x} with {policy:?}: {levels:?}, shrunk to \
+                     {shrunk:?}: recursive says {}, oracle says {}",
+                    recursive(policy, &shrunk),
+                    oracle(policy, &shrunk)
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,187 @@
+use std::io::BufReader;
+
+use aoc::{
+    day3::{execute, interpret, Instruction, Lexeme, Lexer, Machine, Rejection, Token},
+    parse::ParseError,
+};
+
+#[test]
+fn lexer() {
+    let memory = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+    let tokens = Lexer::new(memory.as_bytes())
+        .collect::<anyhow::Result<Vec<_>>>()
+        .unwrap();
+
+    let token = |offset, instruction| Token {
+        offset,
+        instruction,
+    };
+    assert_eq!(
+        tokens,
+        [
+            token(1, Instruction::Mul(2, 4)),
+            token(20, Instruction::Dont),
+            token(28, Instruction::Mul(5, 5)),
+            token(48, Instruction::Mul(11, 8)),
+            token(59, Instruction::Do),
+            token(64, Instruction::Mul(8, 5)),
+        ]
+    );
+    assert_eq!(&memory[20..27], "don't()");
+}
+
+#[test]
+fn machine() {
+    let mut machine = Machine::new(true);
+
+    machine.execute(Instruction::Mul(2, 4)).unwrap();
+    machine.execute(Instruction::Dont).unwrap();
+    machine.execute(Instruction::Mul(5, 5)).unwrap();
+    assert_eq!((machine.enabled, machine.sum), (false, 8));
+
+    machine.execute(Instruction::Do).unwrap();
+    machine.execute(Instruction::Mul(8, 5)).unwrap();
+    assert_eq!((machine.enabled, machine.sum), (true, 48));
+
+    // Without conditions, do() and don't() are ignored
+    let mut machine = Machine::new(false);
+    machine.execute(Instruction::Dont).unwrap();
+    machine.execute(Instruction::Mul(5, 5)).unwrap();
+    assert_eq!((machine.enabled, machine.sum), (true, 25));
+}
+
+#[test]
+fn split_across_reads() {
+    let memory =
+        "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))\nmul(123,4)do";
+    let expected = Lexer::new(memory.as_bytes())
+        .collect::<anyhow::Result<Vec<_>>>()
+        .unwrap();
+
+    // Every instruction starts or ends in the middle of a read
+    for capacity in [1, 2, 3, 5, 8] {
+        let reader = BufReader::with_capacity(capacity, memory.as_bytes());
+        let tokens = Lexer::new(reader)
+            .collect::<anyhow::Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(tokens, expected);
+
+        let reader = BufReader::with_capacity(capacity, memory.as_bytes());
+        assert_eq!(interpret(reader, true).unwrap(), 48 + 492);
+    }
+
+    let memory = "mul(1,2)\né mul(99999999999999999999,2)";
+    let reader = BufReader::with_capacity(3, memory.as_bytes());
+    let err = interpret(reader, false).unwrap_err();
+    let err = err.downcast::<ParseError>().unwrap();
+    assert_eq!((err.line, err.column), (2, 7));
+    assert_eq!(err.text, "99999999999999999999");
+
+    // Operands that fit can still overflow once multiplied or summed
+    let err = interpret("mul(99999999999,99999999999)".as_bytes(), false).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "mul(99999999999,99999999999) overflows the sum"
+    );
+    let mut machine = Machine::new(false);
+    machine.execute(Instruction::Mul(u64::MAX, 1)).unwrap();
+    assert!(machine.execute(Instruction::Mul(1, 1)).is_err());
+}
+
+fn near_misses(memory: &str, capacity: usize, strict: bool) -> Vec<(usize, String, Rejection)> {
+    let reader = BufReader::with_capacity(capacity, memory.as_bytes());
+
+    Lexer::new(reader)
+        .strict(strict)
+        .lexemes()
+        .filter_map(|lexeme| match lexeme.unwrap() {
+            Lexeme::Instruction(_) => None,
+            Lexeme::NearMiss(near_miss) => {
+                Some((near_miss.offset, near_miss.text, near_miss.rejection))
+            }
+        })
+        .collect()
+}
+
+#[test]
+fn strict_mode() {
+    let memory = "mul(123,4)mul(1234,5)mul(6,7890)";
+
+    assert_eq!(
+        interpret(memory.as_bytes(), false).unwrap(),
+        492 + 6170 + 47340
+    );
+    let strict = Lexer::new(memory.as_bytes()).strict(true);
+    assert_eq!(execute(strict, false).unwrap(), 492);
+}
+
+#[test]
+fn near_miss_report() {
+    let memory = "mul[3,7]mul ( 2 , 4 )mul(4*mul(,3)mul(1234,5)mul(8,5)mul(12";
+    let expected = [
+        (
+            0,
+            "mul[",
+            Rejection::Unexpected {
+                found: '[',
+                expected: "`(`",
+            },
+        ),
+        (8, "mul ( 2 , 4 )", Rejection::Whitespace),
+        (
+            21,
+            "mul(4*",
+            Rejection::Unexpected {
+                found: '*',
+                expected: "a digit or `,`",
+            },
+        ),
+        (27, "mul(,3)", Rejection::MissingOperand),
+        (34, "mul(1234,5)", Rejection::TooManyDigits),
+        (53, "mul(12", Rejection::Unterminated),
+    ]
+    .map(|(offset, text, rejection)| (offset, text.to_owned(), rejection));
+
+    for capacity in [1, 4, 64] {
+        assert_eq!(near_misses(memory, capacity, true), expected);
+    }
+
+    // Long operands are fine outside strict mode
+    let lenient = near_misses(memory, 64, false);
+    assert_eq!(lenient.len(), expected.len() - 1);
+    assert!(lenient
+        .iter()
+        .all(|(_, _, rejection)| *rejection != Rejection::TooManyDigits));
+
+    // Oversized operands are reported instead of ending the report
+    let memory = "mul(2,3)mul[1,2]mul(99999999999999999999,2)mul(4*";
+    let unexpected = |found, expected| Rejection::Unexpected { found, expected };
+    let expected = [
+        (8, "mul[", unexpected('[', "`(`")),
+        (
+            16,
+            "mul(99999999999999999999,2)",
+            Rejection::OperandTooLarge,
+        ),
+        (43, "mul(4*", unexpected('*', "a digit or `,`")),
+    ]
+    .map(|(offset, text, rejection)| (offset, text.to_owned(), rejection));
+    for capacity in [1, 4, 64] {
+        assert_eq!(near_misses(memory, capacity, false), expected);
+    }
+    assert_eq!(near_misses(memory, 4, true)[1].2, Rejection::TooManyDigits);
+
+    // Instructions inside a near miss are still found
+    let lexemes = Lexer::new("mul(mul(2,3)".as_bytes())
+        .lexemes()
+        .collect::<anyhow::Result<Vec<_>>>()
+        .unwrap();
+    assert_eq!(lexemes.len(), 2);
+    assert_eq!(
+        lexemes[1],
+        Lexeme::Instruction(Token {
+            offset: 4,
+            instruction: Instruction::Mul(2, 3)
+        })
+    );
+}
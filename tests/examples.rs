@@ -0,0 +1,175 @@
+use aoc::{
+    day1::Day1, day2::Day2, day3::Day3, day4::Day4, day5::Day5, day6::Day6, day7::Day7, day8::Day8,
+    day9::Day9, Solution,
+};
+
+fn part1<S: Solution>(input: &str) -> String {
+    S::parse(input).unwrap().part1().unwrap().to_string()
+}
+
+fn part2<S: Solution>(input: &str) -> String {
+    S::parse(input).unwrap().part2().unwrap().to_string()
+}
+
+#[test]
+fn day1() {
+    const EXAMPLE: &str = "\
+3   4
+4   3
+2   5
+1   3
+3   9
+3   3
+";
+
+    assert_eq!(part1::<Day1>(EXAMPLE), "11");
+    assert_eq!(part2::<Day1>(EXAMPLE), "31");
+}
+
+#[test]
+fn day2() {
+    const EXAMPLE: &str = "\
+7 6 4 2 1
+1 2 7 8 9
+9 7 6 2 1
+1 3 2 4 5
+8 6 4 4 1
+1 3 6 7 9
+";
+
+    assert_eq!(part1::<Day2>(EXAMPLE), "2");
+    assert_eq!(part2::<Day2>(EXAMPLE), "4");
+}
+
+#[test]
+fn day3() {
+    const EXAMPLE_1: &str =
+        "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))";
+    const EXAMPLE_2: &str =
+        "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+
+    assert_eq!(part1::<Day3>(EXAMPLE_1), "161");
+    assert_eq!(part2::<Day3>(EXAMPLE_2), "48");
+}
+
+#[test]
+fn day4() {
+    const EXAMPLE: &str = "\
+MMMSXXMASM
+MSAMXMSMSA
+AMXSXMAAMM
+MSAMASMSMX
+XMASAMXAMM
+XXAMMXXAMA
+SMSMSASXSS
+SAXAMASAAA
+MAMMMXMMMM
+MXMXAXMASX
+";
+
+    assert_eq!(part1::<Day4>(EXAMPLE), "18");
+    assert_eq!(part2::<Day4>(EXAMPLE), "9");
+}
+
+#[test]
+fn day5() {
+    const EXAMPLE: &str = "\
+47|53
+97|13
+97|61
+97|47
+75|29
+61|13
+75|53
+29|13
+97|29
+53|29
+61|53
+97|53
+61|29
+47|13
+75|47
+97|75
+47|61
+75|61
+47|29
+75|13
+53|13
+
+75,47,61,53,29
+97,61,53,29,13
+75,29,13
+75,97,47,61,53
+61,13,29
+97,13,75,29,47
+";
+
+    assert_eq!(part1::<Day5>(EXAMPLE), "143");
+    assert_eq!(part2::<Day5>(EXAMPLE), "123");
+}
+
+#[test]
+fn day6() {
+    const EXAMPLE: &str = "\
+....#.....
+.........#
+..........
+..#.......
+.......#..
+..........
+.#..^.....
+........#.
+#.........
+......#...
+";
+
+    assert_eq!(part1::<Day6>(EXAMPLE), "41");
+    assert_eq!(part2::<Day6>(EXAMPLE), "6");
+}
+
+#[test]
+fn day7() {
+    const EXAMPLE: &str = "\
+190: 10 19
+3267: 81 40 27
+83: 17 5
+156: 15 6
+7290: 6 8 6 15
+161011: 16 10 13
+192: 17 8 14
+21037: 9 7 18 13
+292: 11 6 16 20
+";
+
+    assert_eq!(part1::<Day7>(EXAMPLE), "3749");
+    assert_eq!(part2::<Day7>(EXAMPLE), "11387");
+}
+
+#[test]
+fn day8() {
+    const EXAMPLE: &str = "\
+............
+........0...
+.....0......
+.......0....
+....0.......
+......A.....
+............
+............
+........A...
+.........A..
+............
+............
+";
+
+    assert_eq!(part1::<Day8>(EXAMPLE), "14");
+    assert_eq!(part2::<Day8>(EXAMPLE), "34");
+}
+
+#[test]
+fn day9() {
+    const EXAMPLE: &str = "2333133121414131402\n";
+
+    // Part 2 is not solved yet
+    assert_eq!(part1::<Day9>(EXAMPLE), "1928");
+}
@@ -0,0 +1,30 @@
+use aoc::external_sort::ExternalSorter;
+
+#[test]
+fn sorts_across_runs() {
+    let temp_dir = std::env::temp_dir();
+    let mut sorter = ExternalSorter::new(&temp_dir, 3).unwrap();
+
+    // Enough runs of 3 values to need an intermediate merge
+    let values = (0..1000)
+        .map(|i| (i * 7919) % 1000 - 500)
+        .collect::<Vec<i128>>();
+    for &value in &values {
+        sorter.push(value).unwrap();
+    }
+    let runs = sorter.finish().unwrap();
+
+    let mut expected = values;
+    expected.sort();
+    for _ in 0..2 {
+        let sorted = runs.iter().unwrap().collect::<anyhow::Result<Vec<_>>>();
+        assert_eq!(sorted.unwrap(), expected);
+    }
+}
+
+#[test]
+fn empty() {
+    let sorter = ExternalSorter::new(&std::env::temp_dir(), 4).unwrap();
+
+    assert_eq!(sorter.finish().unwrap().iter().unwrap().count(), 0);
+}
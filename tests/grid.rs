@@ -0,0 +1,80 @@
+use aoc::{
+    grid::{Direction, Grid},
+    parse::ParseError,
+};
+
+fn grid() -> Grid<char> {
+    Grid::parse("abc\ndef\n", 0, |_, _, c| Ok(c)).unwrap()
+}
+
+fn collect<'a>(line: impl Iterator<Item = &'a char>) -> String {
+    line.collect()
+}
+
+#[test]
+fn parse() {
+    let grid = grid();
+
+    assert_eq!(grid.width(), 3);
+    assert_eq!(grid.height(), 2);
+    assert_eq!(grid.get(2, 1), Some(&'f'));
+    assert_eq!(grid.get(3, 0), None);
+    assert_eq!(grid.get(0, 2), None);
+    assert_eq!(grid[(1, 0)], 'b');
+}
+
+#[test]
+fn parse_ragged() {
+    let err = Grid::parse("abc\nde\n", 4, |_, _, c| Ok(c)).unwrap_err();
+    let err = err.downcast::<ParseError>().unwrap();
+
+    assert_eq!((err.day, err.line, err.column), (4, 2, 3));
+    assert_eq!(err.text, "de");
+}
+
+#[test]
+fn views() {
+    let grid = grid();
+
+    assert_eq!(grid.row(1), ['d', 'e', 'f']);
+    assert_eq!(collect(grid.column(1)), "be");
+    assert_eq!(collect(grid.line(0, 1, Direction::UpRight)), "db");
+
+    let lines = |direction| grid.lines(direction).map(collect).collect::<Vec<_>>();
+    assert_eq!(lines(Direction::Right), ["abc", "def"]);
+    assert_eq!(lines(Direction::Down), ["ad", "be", "cf"]);
+    assert_eq!(lines(Direction::DownRight), ["ae", "bf", "c", "d"]);
+    assert_eq!(lines(Direction::DownLeft), ["a", "bd", "ce", "f"]);
+}
+
+#[test]
+fn neighbours() {
+    let grid = grid();
+
+    let neighbours = grid.neighbours(0, 0).collect::<Vec<_>>();
+    assert_eq!(
+        neighbours,
+        [
+            (Direction::Right, (1, 0)),
+            (Direction::DownRight, (1, 1)),
+            (Direction::Down, (0, 1)),
+        ]
+    );
+
+    assert_eq!(grid.step(2, 1, Direction::Right), None);
+    assert_eq!(grid.position(-1, 0), None);
+}
+
+#[test]
+fn turn_right() {
+    let mut direction = Direction::Up;
+    for expected in [
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+        Direction::Up,
+    ] {
+        direction = direction.turn_right();
+        assert_eq!(direction, expected);
+    }
+}
@@ -0,0 +1,83 @@
+use aoc::{
+    day1::Day1, day2::Day2, day3::Day3, day4::Day4, day5::Day5, day6::Day6, day7::Day7, day8::Day8,
+    day9::Day9, Solution,
+};
+
+fn solve<S: Solution>() -> S {
+    let input = format!("{}/{}.input", env!("CARGO_MANIFEST_DIR"), S::DAY);
+    let input = std::fs::read_to_string(input).unwrap();
+
+    S::parse(&input).unwrap()
+}
+
+#[test]
+fn day1() {
+    let solution = solve::<Day1>();
+
+    assert_eq!(solution.part1().unwrap(), 1646452);
+    assert_eq!(solution.part2().unwrap(), 23609874);
+}
+
+#[test]
+fn day2() {
+    let solution = solve::<Day2>();
+
+    assert_eq!(solution.part1().unwrap(), 269);
+    assert_eq!(solution.part2().unwrap(), 337);
+}
+
+#[test]
+fn day3() {
+    let solution = solve::<Day3>();
+
+    assert_eq!(solution.part1().unwrap(), 163931492);
+    assert_eq!(solution.part2().unwrap(), 76911921);
+}
+
+#[test]
+fn day4() {
+    let solution = solve::<Day4>();
+
+    assert_eq!(solution.part1().unwrap(), 2414);
+    assert_eq!(solution.part2().unwrap(), 1871);
+}
+
+#[test]
+fn day5() {
+    let solution = solve::<Day5>();
+
+    assert_eq!(solution.part1().unwrap(), 4957);
+    assert_eq!(solution.part2().unwrap(), 6938);
+}
+
+#[test]
+fn day6() {
+    let solution = solve::<Day6>();
+
+    assert_eq!(solution.part1().unwrap(), 4665);
+    assert_eq!(solution.part2().unwrap(), 1688);
+}
+
+#[test]
+fn day7() {
+    let solution = solve::<Day7>();
+
+    assert_eq!(solution.part1().unwrap(), 1985268524462);
+    assert_eq!(solution.part2().unwrap(), 150077710195188);
+}
+
+#[test]
+fn day8() {
+    let solution = solve::<Day8>();
+
+    assert_eq!(solution.part1().unwrap(), 295);
+    assert_eq!(solution.part2().unwrap(), 1034);
+}
+
+#[test]
+fn day9() {
+    let solution = solve::<Day9>();
+
+    // Part 2 is not solved yet
+    assert_eq!(solution.part1().unwrap(), 6283404590840);
+}
@@ -0,0 +1,65 @@
+use std::time::Duration;
+
+use aoc::{
+    cli::write_json,
+    runner::{single_pass, DayResult, Part, PartResult},
+};
+
+#[test]
+fn records() {
+    let results = [
+        DayResult {
+            day: 1,
+            parse_elapsed: Duration::from_nanos(300),
+            parts: vec![PartResult {
+                day: 1,
+                part: Part::One,
+                answer: Ok("11".to_owned()),
+                elapsed: Duration::from_nanos(1500),
+            }],
+        },
+        DayResult {
+            day: 9,
+            parse_elapsed: Duration::from_nanos(100),
+            parts: vec![PartResult {
+                day: 9,
+                part: Part::Two,
+                answer: Err(anyhow::anyhow!("not \"implemented\"")),
+                elapsed: Duration::from_nanos(20),
+            }],
+        },
+    ];
+
+    let mut json = Vec::new();
+    write_json(&mut json, &results).unwrap();
+
+    assert_eq!(
+        String::from_utf8(json).unwrap(),
+        r#"[
+  {"day":1,"part":1,"answer":"11","elapsed_ns":1500,"parse_ns":300},
+  {"day":9,"part":2,"answer":null,"error":"not \"implemented\"","elapsed_ns":20,"parse_ns":100}
+]
+"#
+    );
+}
+
+#[test]
+fn single_pass_records() {
+    let result = single_pass(
+        2,
+        Duration::from_nanos(700),
+        [Ok("2".to_owned()), Ok("4".to_owned())],
+    );
+
+    let mut json = Vec::new();
+    write_json(&mut json, &[result]).unwrap();
+
+    assert_eq!(
+        String::from_utf8(json).unwrap(),
+        r#"[
+  {"day":2,"part":1,"answer":"2","elapsed_ns":0,"parse_ns":700},
+  {"day":2,"part":2,"answer":"4","elapsed_ns":0,"parse_ns":700}
+]
+"#
+    );
+}
@@ -0,0 +1,130 @@
+use aoc::{
+    day1::Day1, day2::Day2, day3::Day3, day4::Day4, day5::Day5, day6::Day6, day7::Day7, day9::Day9,
+    parse::ParseError, Solution,
+};
+
+fn parse_error<S: Solution>(input: &str) -> ParseError {
+    let Err(err) = S::parse(input) else {
+        panic!("day {} input should be rejected", S::DAY);
+    };
+
+    err.downcast::<ParseError>().unwrap()
+}
+
+fn position(err: &ParseError) -> (u32, usize, usize, &str) {
+    (err.day, err.line, err.column, err.text.as_str())
+}
+
+#[test]
+fn day1() {
+    assert_eq!(
+        position(&parse_error::<Day1>("3   4\n4   x3\n")),
+        (1, 2, 5, "x3")
+    );
+    assert_eq!(position(&parse_error::<Day1>("3   4\n4\n")), (1, 2, 2, "4"));
+    assert_eq!(position(&parse_error::<Day1>("3\n")), (1, 1, 2, "3"));
+    assert_eq!(
+        position(&parse_error::<Day1>("3   4\n4   3   5\n")),
+        (1, 2, 9, "5")
+    );
+}
+
+#[test]
+fn day2() {
+    assert_eq!(
+        position(&parse_error::<Day2>("7 6 4\n1 2 -7 8\n")),
+        (2, 2, 5, "-7")
+    );
+}
+
+#[test]
+fn day3() {
+    assert_eq!(
+        position(&parse_error::<Day3>(
+            "mul(1,2)\n mul(99999999999999999999,2)"
+        )),
+        (3, 2, 6, "99999999999999999999")
+    );
+}
+
+#[test]
+fn day4() {
+    assert_eq!(
+        position(&parse_error::<Day4>("XMAS\nXMA\n")),
+        (4, 2, 4, "XMA")
+    );
+}
+
+#[test]
+fn day5() {
+    assert_eq!(
+        position(&parse_error::<Day5>("47|53\n97\n\n75,47\n")),
+        (5, 2, 1, "97")
+    );
+    assert_eq!(
+        position(&parse_error::<Day5>("47|53\n\n75,4a7\n")),
+        (5, 3, 4, "4a7")
+    );
+    assert_eq!(
+        position(&parse_error::<Day5>("47||53\n\n75,47\n")),
+        (5, 1, 1, "47||53")
+    );
+    assert_eq!(
+        position(&parse_error::<Day5>("47|\n\n75,47\n")),
+        (5, 1, 4, "")
+    );
+    assert_eq!(
+        position(&parse_error::<Day5>("47|53\n\n,\n")),
+        (5, 3, 1, "")
+    );
+    assert_eq!(
+        position(&parse_error::<Day5>("47|53\n\n47,,53\n")),
+        (5, 3, 4, "")
+    );
+}
+
+#[test]
+fn day6() {
+    let err = parse_error::<Day6>("..#\n.x^\n");
+
+    assert_eq!(position(&err), (6, 2, 2, "x"));
+    assert_eq!(err.reason, "invalid tile");
+
+    let err = parse_error::<Day6>("^.#\n..>\n");
+    assert_eq!(position(&err), (6, 2, 3, ">"));
+    assert_eq!(err.reason, "second guard");
+
+    let err = parse_error::<Day6>("....\n.#..\n");
+    assert_eq!(position(&err), (6, 1, 1, ""));
+    assert_eq!(err.reason, "no guard");
+
+    let err = parse_error::<Day6>("");
+    assert_eq!(position(&err), (6, 1, 1, ""));
+    assert_eq!(err.reason, "empty map");
+}
+
+#[test]
+fn day7() {
+    assert_eq!(
+        position(&parse_error::<Day7>("190: 10 19\n3267 81\n")),
+        (7, 2, 5, " ")
+    );
+    assert_eq!(position(&parse_error::<Day7>("190: 10 \n")), (7, 1, 9, ""));
+}
+
+#[test]
+fn day9() {
+    assert_eq!(position(&parse_error::<Day9>("23a3\n")), (9, 1, 3, "a"));
+    assert_eq!(position(&parse_error::<Day9>("210\n")), (9, 1, 3, "0"));
+    assert_eq!(position(&parse_error::<Day9>("23\n\n")), (9, 2, 1, "\n"));
+}
+
+#[test]
+fn display() {
+    let err = ParseError::new(2, 3, 4, "x", "invalid number");
+
+    assert_eq!(
+        err.to_string(),
+        "day 2 input, line 3, column 4: invalid number: \"x\""
+    );
+}
@@ -0,0 +1,38 @@
+use aoc::{
+    day1::Day1,
+    runner::{bench, run, run_day, Part},
+};
+
+const EXAMPLE: &str = "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n";
+
+#[test]
+fn run_single_part() {
+    let result = run::<Day1>(EXAMPLE, Some(Part::Two)).unwrap();
+
+    assert_eq!(result.day, 1);
+    assert_eq!(result.parts.len(), 1);
+    assert_eq!(result.parts[0].part, Part::Two);
+    assert_eq!(result.parts[0].answer.as_ref().unwrap(), "31");
+}
+
+#[test]
+fn run_unsolved_day() {
+    assert!(run_day(25, EXAMPLE, None).is_err());
+}
+
+#[test]
+fn bench_stages() {
+    let timings = bench::<Day1>(EXAMPLE, None, 5).unwrap();
+
+    let stages = timings
+        .iter()
+        .map(|timings| timings.part)
+        .collect::<Vec<_>>();
+    assert_eq!(stages, [None, Some(Part::One), Some(Part::Two)]);
+
+    for timings in &timings {
+        assert!(timings.error.is_none());
+        assert!(timings.min() <= timings.median());
+        assert!(timings.median() <= timings.max());
+    }
+}